@@ -0,0 +1,55 @@
+use error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Replace the file at `path` with what `write` writes, all or nothing. The
+/// contents go to a temporary file next to it, unique to the process and the
+/// call, which is synced to the disk and renamed over `path`, and then the
+/// directory is synced so the rename survives a crash too.
+pub(crate) fn write<F>(path: &Path, write: F) -> Result<(), Error>
+where
+    F: FnOnce(&mut File) -> Result<(), Error>,
+{
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = PathBuf::from(tmp);
+
+    let written = File::create(&tmp)
+        .map_err(Error::from)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            Ok(file.sync_all()?)
+        })
+        .and_then(|_| Ok(fs::rename(&tmp, path)?));
+
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    sync_dir(path)
+}
+
+/// Sync the directory holding `path`. Directories can't be opened as files on
+/// every platform, so this only syncs on Unix.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> Result<(), Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    Ok(File::open(dir)?.sync_all()?)
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
@@ -0,0 +1,144 @@
+/// Caverphone 2.0 phonetic encoding, always ten characters long.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::caverphone::encoding("Thompson"), "TMPSN11111");
+/// assert_eq!(ttaw::caverphone::encoding("Stevenson"), "STFNSN1111");
+/// ```
+pub fn encoding(input: &str) -> String {
+    let mut word = input
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<String>();
+
+    if word.is_empty() {
+        return String::new();
+    }
+
+    word = replace_end(&word, "e", "");
+
+    for (from, to) in &[
+        ("cough", "cou2f"),
+        ("rough", "rou2f"),
+        ("tough", "tou2f"),
+        ("enough", "enou2f"),
+        ("trough", "trou2f"),
+        ("gn", "2n"),
+    ] {
+        word = replace_start(&word, from, to);
+    }
+
+    word = replace_end(&word, "mb", "m2");
+
+    for (from, to) in &[
+        ("cq", "2q"),
+        ("ci", "si"),
+        ("ce", "se"),
+        ("cy", "sy"),
+        ("tch", "2ch"),
+        ("c", "k"),
+        ("q", "k"),
+        ("x", "k"),
+        ("v", "f"),
+        ("dg", "2g"),
+        ("tio", "sio"),
+        ("tia", "sia"),
+        ("d", "t"),
+        ("ph", "fh"),
+        ("b", "p"),
+        ("sh", "s2"),
+        ("z", "s"),
+    ] {
+        word = word.replace(from, to);
+    }
+
+    if word.starts_with(is_vowel) {
+        word.replace_range(..1, "A");
+    }
+    word = word.replace(is_vowel, "3");
+
+    word = word.replace('j', "y");
+    word = replace_start(&word, "y3", "Y3");
+    word = replace_start(&word, "y", "A");
+    word = word.replace('y', "3");
+
+    word = word.replace("3gh3", "3kh3");
+    word = word.replace("gh", "22");
+    word = word.replace('g', "k");
+
+    for (from, to) in &[
+        ('s', 'S'),
+        ('t', 'T'),
+        ('p', 'P'),
+        ('k', 'K'),
+        ('f', 'F'),
+        ('m', 'M'),
+        ('n', 'N'),
+    ] {
+        word = squeeze(&word, *from, *to);
+    }
+
+    word = word.replace("w3", "W3");
+    word = word.replace("wh3", "Wh3");
+    word = replace_end(&word, "w", "3");
+    word = word.replace('w', "2");
+
+    word = replace_start(&word, "h", "A");
+    word = word.replace('h', "2");
+
+    word = word.replace("r3", "R3");
+    word = replace_end(&word, "r", "3");
+    word = word.replace('r', "2");
+
+    word = word.replace("l3", "L3");
+    word = replace_end(&word, "l", "3");
+    word = word.replace('l', "2");
+
+    word = word.replace('2', "");
+    word = replace_end(&word, "3", "A");
+    word = word.replace('3', "");
+
+    word.push_str("1111111111");
+    word.truncate(10);
+    word
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn replace_start(word: &str, from: &str, to: &str) -> String {
+    match word.strip_prefix(from) {
+        Some(rest) => format!("{}{}", to, rest),
+        None => word.to_string(),
+    }
+}
+
+fn replace_end(word: &str, from: &str, to: &str) -> String {
+    match word.strip_suffix(from) {
+        Some(rest) => format!("{}{}", rest, to),
+        None => word.to_string(),
+    }
+}
+
+/// Replace each run of `from` with a single `to`.
+fn squeeze(word: &str, from: char, to: char) -> String {
+    let mut squeezed = String::with_capacity(word.len());
+    let mut in_run = false;
+
+    for c in word.chars() {
+        if c == from {
+            if !in_run {
+                squeezed.push(to);
+            }
+            in_run = true;
+        } else {
+            squeezed.push(c);
+            in_run = false;
+        }
+    }
+
+    squeezed
+}
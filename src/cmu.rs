@@ -1,26 +1,477 @@
 extern crate pest;
 
+use atomic;
+use batch;
+use dialect::Dialect;
 use error::Error;
 use metaphone::{Rule, Word};
+use morphology::{self, ends_in_sibilant, Suffix};
 use pest::Parser;
+use phoneme::Phoneme;
+use phonetic::Verdict;
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::env;
+use std::fmt;
 use std::fs;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+/// Environment variable with the path of the default dictionary.
+pub const PATH_VAR: &str = "TTAW_CMUDICT";
+
+/// A handle to a loaded CMU dictionary. Clones share the same dictionary, so
+/// a handle can be cloned into every thread or service that needs it.
+#[derive(Clone)]
 pub struct CmuDict {
-    dict: HashMap<String, Vec<Vec<String>>>,
+    dict: Arc<Dictionary>,
+    normalizer: Normalizer,
+    dialect: Dialect,
+    rhyme_options: RhymeOptions,
+}
+
+/// The dictionary held compactly: the phonemes of every pronunciation, one
+/// byte each, in a single arena.
+struct Dictionary {
+    /// Word to its range in `pronunciations`.
+    words: HashMap<Box<str>, (u32, u32)>,
+    /// Start of each pronunciation in `phonemes`, followed by the end of the
+    /// last one.
+    pronunciations: Vec<u32>,
+    phonemes: Vec<Phoneme>,
+}
+
+impl Dictionary {
+    /// Parse the JSON map of words to their pronunciations straight into the
+    /// arena, `origin` naming where it came from in errors.
+    fn from_json<O: fmt::Display>(json: &str, origin: O) -> Result<Dictionary, Error> {
+        let mut dict = Dictionary {
+            words: HashMap::new(),
+            pronunciations: vec![0],
+            phonemes: vec![],
+        };
+        let mut failure = None;
+
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let parsed = Entries {
+            dict: &mut dict,
+            failure: &mut failure,
+        }
+        .deserialize(&mut deserializer)
+        .and_then(|()| deserializer.end());
+
+        if let Err(e) = parsed {
+            return Err(match failure {
+                Some(failure) => Error::CorruptDictionary {
+                    origin: origin.to_string(),
+                    line: Some(e.line()),
+                    source: Box::new(failure),
+                },
+                None => corrupt(origin, e.into()),
+            });
+        }
+
+        dict.phonemes.shrink_to_fit();
+        dict.pronunciations.shrink_to_fit();
+        Ok(dict)
+    }
+
+    fn get(&self, word: &str) -> Option<Pronunciations<'_>> {
+        self.words.get(word).map(|&(start, end)| Pronunciations {
+            dict: self,
+            next: start as usize,
+            end: end as usize,
+            derivation: Derivation::Word,
+        })
+    }
+
+    fn pronunciation(&self, i: usize) -> &[Phoneme] {
+        let start = self.pronunciations[i] as usize;
+        let end = self.pronunciations[i + 1] as usize;
+        &self.phonemes[start..end]
+    }
+
+    /// Look up a normalized word, deriving the pronunciation of an unknown
+    /// word from its stem or the parts of a compound as the normalizer allows.
+    fn find(&self, word: &str, normalizer: &Normalizer) -> Option<Pronunciations<'_>> {
+        if let Some(pronunciations) = self.get(word) {
+            return Some(pronunciations);
+        }
+
+        if normalizer.suffixes {
+            for stem in morphology::stems(word) {
+                if let Some(pronunciations) = self.get(&stem.word) {
+                    if stem.sibilant && !pronunciations.clone().all(|p| ends_in_sibilant(&p)) {
+                        continue;
+                    }
+
+                    return Some(Pronunciations {
+                        derivation: Derivation::Suffix(stem.suffix),
+                        ..pronunciations
+                    });
+                }
+            }
+        }
+
+        if normalizer.compounds {
+            for (first, second) in morphology::compound_splits(word) {
+                if let (Some(first), Some(second)) = (self.get(first), self.get(second)) {
+                    return Some(Pronunciations {
+                        derivation: Derivation::Compound {
+                            start: second.next,
+                            next: second.next,
+                            end: second.end,
+                        },
+                        ..first
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Deserializes the entries of the JSON dictionary into a `Dictionary`,
+/// keeping errors in the entries themselves in `failure` rather than as
+/// JSON errors.
+struct Entries<'a> {
+    dict: &'a mut Dictionary,
+    failure: &'a mut Option<Error>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for Entries<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for Entries<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of words to their pronunciations")
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<(), M::Error> {
+        self.dict.words.reserve(map.size_hint().unwrap_or(0));
+
+        while let Some(word) = map.next_key::<String>()? {
+            let start = self.dict.pronunciations.len() - 1;
+
+            map.next_value_seed(Arena {
+                dict: &mut *self.dict,
+                failure: &mut *self.failure,
+                pronunciations: true,
+            })?;
+
+            let end = self.dict.pronunciations.len() - 1;
+
+            // every word needs a pronunciation for derived words to build on
+            if start == end || (start..end).any(|i| self.dict.pronunciation(i).is_empty()) {
+                *self.failure = Some(Error::EmptyPronunciation(word));
+                return Err(de::Error::custom("empty pronunciation"));
+            }
+
+            self.dict
+                .words
+                .insert(word.into_boxed_str(), (start as u32, end as u32));
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes the pronunciations of a word, or with `pronunciations` off
+/// the phonemes of one pronunciation, onto the end of the arena.
+struct Arena<'a> {
+    dict: &'a mut Dictionary,
+    failure: &'a mut Option<Error>,
+    pronunciations: bool,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for Arena<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for Arena<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.pronunciations {
+            "a list of pronunciations"
+        } else {
+            "a list of phonemes"
+        })
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<(), S::Error> {
+        if self.pronunciations {
+            while let Some(()) = seq.next_element_seed(Arena {
+                dict: &mut *self.dict,
+                failure: &mut *self.failure,
+                pronunciations: false,
+            })? {
+                self.dict
+                    .pronunciations
+                    .push(self.dict.phonemes.len() as u32);
+            }
+            return Ok(());
+        }
+
+        while let Some(Symbol(phoneme)) = seq.next_element()? {
+            match phoneme {
+                Ok(phoneme) => self.dict.phonemes.push(phoneme),
+                Err(e) => {
+                    *self.failure = Some(e);
+                    return Err(de::Error::custom("invalid phoneme"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A phoneme in the JSON dictionary, parsed without copying the symbol.
+struct Symbol(Result<Phoneme, Error>);
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Symbol, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor)
+    }
+}
+
+struct SymbolVisitor;
+
+impl<'de> Visitor<'de> for SymbolVisitor {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an ARPAbet phoneme")
+    }
+
+    fn visit_str<E: de::Error>(self, symbol: &str) -> Result<Symbol, E> {
+        Ok(Symbol(symbol.parse()))
+    }
+}
+
+/// How the pronunciations of a word not in the dictionary are derived.
+#[derive(Clone, Copy)]
+enum Derivation {
+    Word,
+    /// The stem's pronunciations followed by the suffix.
+    Suffix(Suffix),
+    /// Every pronunciation of the first part followed by every one of the
+    /// second part, `next` of `start..end`.
+    Compound {
+        start: usize,
+        next: usize,
+        end: usize,
+    },
+}
+
+/// The pronunciations of a word, each a slice of phonemes borrowed from the
+/// dictionary. Pronunciations of inflections and compounds that aren't in the
+/// dictionary are derived from their parts, so they're owned.
+#[derive(Clone)]
+pub struct Pronunciations<'a> {
+    dict: &'a Dictionary,
+    next: usize,
+    end: usize,
+    derivation: Derivation,
+}
+
+impl<'a> Iterator for Pronunciations<'a> {
+    type Item = Cow<'a, [Phoneme]>;
+
+    fn next(&mut self) -> Option<Cow<'a, [Phoneme]>> {
+        if self.next >= self.end {
+            return None;
+        }
+
+        let dict = self.dict;
+        let phones = dict.pronunciation(self.next);
+
+        match self.derivation {
+            Derivation::Word => {
+                self.next += 1;
+                Some(Cow::Borrowed(phones))
+            }
+            Derivation::Suffix(suffix) => {
+                self.next += 1;
+                let mut phones = phones.to_vec();
+                phones.extend(suffix.phonemes(phones.last().copied()));
+                Some(Cow::Owned(phones))
+            }
+            Derivation::Compound {
+                start,
+                ref mut next,
+                end,
+            } => {
+                let mut phones = phones.to_vec();
+                phones.extend(
+                    dict.pronunciation(*next)
+                        .iter()
+                        .map(|p| morphology::demote(*p)),
+                );
+
+                *next += 1;
+                if *next == end {
+                    *next = start;
+                    self.next += 1;
+                }
+
+                Some(Cow::Owned(phones))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.derivation {
+            Derivation::Compound { start, next, end } => {
+                (self.end - self.next) * (end - start) - (next - start)
+            }
+            _ => self.end - self.next,
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a> Pronunciations<'a> {
+    /// Whether the pronunciations are derived from a stem or the parts of a
+    /// compound rather than the dictionary's own.
+    pub fn is_derived(&self) -> bool {
+        !matches!(self.derivation, Derivation::Word)
+    }
 }
 
+impl<'a> ExactSizeIterator for Pronunciations<'a> {}
+
 impl CmuDict {
     /// Initialize the CmuDict with a path to the existing serialized CMU dictionary
     /// or a directoy containing it. If the dictionary doesn't exisit, it will be
     /// downloaded and serialized at the location specified by the path parameter.
-    pub fn new(path: &str) -> Result<CmuDict, Error> {
-        match from_json_file(Path::new(path)) {
-            Ok(d) => Ok(CmuDict { dict: d }),
-            Err(e) => Err(e),
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<CmuDict, Error> {
+        CmuDict::from_source(path, &Source::from_env())
+    }
+
+    /// Like `new`, downloading the dictionary from `source` if it isn't at `path`.
+    pub fn from_source<P: AsRef<Path>>(path: P, source: &Source) -> Result<CmuDict, Error> {
+        let path = dictionary_file(path.as_ref());
+
+        Ok(CmuDict {
+            dict: Arc::new(from_json_file(&path, source)?),
+            normalizer: Normalizer::default(),
+            dialect: Dialect::default(),
+            rhyme_options: RhymeOptions::default(),
+        })
+    }
+
+    /// A handle to the same dictionary normalizing words with `normalizer`.
+    ///
+    /// ```rust,no_run
+    /// extern crate ttaw;
+    /// use ttaw::cmu::{CmuDict, Normalizer};
+    /// let cmudict = CmuDict::new("cmudict.json").unwrap();
+    /// assert!(cmudict.encoding("Dog's").unwrap().is_some());
+    ///
+    /// let exact = cmudict.with_normalizer(Normalizer::exact());
+    /// assert!(exact.encoding("Dog's").unwrap().is_none());
+    /// ```
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> CmuDict {
+        self.normalizer = normalizer;
+        self
+    }
+
+    pub fn normalizer(&self) -> &Normalizer {
+        &self.normalizer
+    }
+
+    /// A handle to the same dictionary comparing rhymes and alliteration in
+    /// `dialect`, see `Dialect`.
+    pub fn with_dialect(mut self, dialect: Dialect) -> CmuDict {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn dialect(&self) -> &Dialect {
+        &self.dialect
+    }
+
+    /// A handle to the same dictionary telling rhymes as strictly as
+    /// `options` say, see `RhymeOptions`.
+    ///
+    /// ```rust,no_run
+    /// extern crate ttaw;
+    /// use ttaw::cmu::{CmuDict, RhymeOptions};
+    /// let cmudict = CmuDict::new("cmudict.json").unwrap();
+    /// assert!(!cmudict.rhyme("hear", "near").unwrap());
+    ///
+    /// let loose = cmudict.with_rhyme_options(RhymeOptions::loose());
+    /// assert!(loose.rhyme("hear", "near").unwrap());
+    /// ```
+    pub fn with_rhyme_options(mut self, options: RhymeOptions) -> CmuDict {
+        self.rhyme_options = options;
+        self
+    }
+
+    pub fn rhyme_options(&self) -> &RhymeOptions {
+        &self.rhyme_options
+    }
+
+    /// Normalize and look up a word.
+    fn find(&self, w: &str) -> Option<Pronunciations<'_>> {
+        let word = self.normalizer.normalized(w);
+        if !self.normalizer.punctuation {
+            return self.dict.find(&word, &self.normalizer);
         }
+
+        // apostrophes around a word are more often quotes than part of it
+        let bare = word.trim_matches('\'');
+        if bare.len() != word.len() {
+            if let Some(pronunciations) = self.dict.get(&word) {
+                return Some(pronunciations);
+            }
+        }
+        self.dict.find(bare, &self.normalizer)
+    }
+
+    /// A builder to choose where the dictionary is loaded from.
+    pub fn builder() -> CmuDictBuilder {
+        CmuDictBuilder::default()
+    }
+
+    /// The process-wide default dictionary, loaded from `default_path` on first
+    /// use and shared by every caller after that. It's the dictionary a default
+    /// `CmuDictBuilder` loads, unless the `TTAW_CMUDICT` environment variable
+    /// names another.
+    pub fn global() -> Result<CmuDict, Error> {
+        static GLOBAL: Mutex<Option<CmuDict>> = Mutex::new(None);
+
+        // a panic while loading leaves nothing behind to be inconsistent
+        let mut global = GLOBAL.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(dict) = global.as_ref() {
+            return Ok(dict.clone());
+        }
+
+        let builder = match env::var_os(PATH_VAR).filter(|p| !p.is_empty()) {
+            Some(path) => CmuDict::builder().no_cache().path(path),
+            None => CmuDict::builder(),
+        };
+
+        let dict = builder.build()?;
+        *global = Some(dict.clone());
+        Ok(dict)
     }
 
     /// CMUdict phonetic encoding.
@@ -58,7 +509,73 @@ impl CmuDict {
     /// );
     /// ```
     pub fn encoding(&self, w: &str) -> Result<Option<Vec<Vec<String>>>, Error> {
-        Ok(self.dict.get(w).map(|v| v.to_vec()))
+        Ok(self.find(w).map(|pronunciations| {
+            pronunciations
+                .map(|p| p.iter().map(|phoneme| phoneme.to_string()).collect())
+                .collect()
+        }))
+    }
+
+    /// CMUdict pronunciations of a word, borrowed from the dictionary rather
+    /// than copied like with `encoding`. Like every lookup the word is
+    /// normalized first, see `Normalizer`.
+    ///
+    /// ```rust,no_run
+    /// extern crate ttaw;
+    /// use ttaw::cmu::CmuDict;
+    /// let cmudict = CmuDict::new("cmudict.json").unwrap();
+    /// let pronunciation = cmudict.pronunciations("unearthed").unwrap().next().unwrap();
+    /// assert_eq!(pronunciation.len(), 5);
+    /// assert_eq!(pronunciation[2].as_str(), "ER1");
+    /// ```
+    pub fn pronunciations(&self, w: &str) -> Option<Pronunciations<'_>> {
+        self.find(w)
+    }
+
+    /// Like `pronunciations`, with an `UnknownWord` error for a word that isn't
+    /// in the dictionary.
+    pub fn lookup(&self, w: &str) -> Result<Pronunciations<'_>, Error> {
+        self.find(w)
+            .ok_or_else(|| Error::UnknownWord(w.to_string()))
+    }
+
+    /// Number of syllables in a word, the vowels of its first pronunciation.
+    ///
+    /// ```rust,no_run
+    /// extern crate ttaw;
+    /// use ttaw::cmu::CmuDict;
+    /// let cmudict = CmuDict::new("cmudict.json").unwrap();
+    /// assert_eq!(cmudict.syllables("unearthed").unwrap(), Some(2));
+    /// assert_eq!(cmudict.syllables("unearthing").unwrap(), Some(3));
+    /// assert_eq!(cmudict.syllables("zzzz").unwrap(), None);
+    /// ```
+    pub fn syllables(&self, w: &str) -> Result<Option<usize>, Error> {
+        Ok(self
+            .find(w)
+            .and_then(|mut pronunciations| pronunciations.next())
+            .map(|p| p.iter().filter(|p| p.is_vowel()).count()))
+    }
+
+    /// CMUdict phonetic encodings of many words, in the order given. With the
+    /// `parallel` feature the words are looked up across threads.
+    ///
+    /// ```rust,no_run
+    /// extern crate ttaw;
+    /// use ttaw::cmu::CmuDict;
+    /// let cmudict = CmuDict::new("cmudict.json").unwrap();
+    /// let encodings = cmudict.encoding_batch(vec!["unearthed", "zzzz"]).unwrap();
+    /// assert!(encodings[0].is_some());
+    /// assert_eq!(encodings[1], None);
+    /// ```
+    pub fn encoding_batch<I, S>(&self, words: I) -> Result<Vec<Option<Vec<Vec<String>>>>, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str> + Sync,
+    {
+        let words = words.into_iter().collect::<Vec<S>>();
+        batch::map(&words, |word| self.encoding(word.as_ref()))
+            .into_iter()
+            .collect()
     }
 
     /// Use CMUdict phonetic encoding to determine if two words rhyme.
@@ -76,14 +593,26 @@ impl CmuDict {
     /// assert!(!cmudict.rhyme("comfy", "chair").unwrap());
     /// ```
     pub fn rhyme(&self, a: &str, b: &str) -> Result<bool, Error> {
-        if let (Some(phones_a), Some(phones_b)) = (
-            self.dict.get(a.to_string().to_lowercase().trim()),
-            self.dict.get(b.to_string().to_lowercase().trim()),
-        ) {
-            return Ok(eval_rhyme(phones_a, phones_b));
-        }
+        Ok(self.rhyme_verdict(a, b)?.is_yes())
+    }
 
-        Ok(false)
+    /// Like `rhyme`, telling words that don't rhyme apart from words that
+    /// aren't in the dictionary.
+    ///
+    /// ```rust,no_run
+    /// extern crate ttaw;
+    /// use ttaw::cmu::CmuDict;
+    /// use ttaw::phonetic::Verdict;
+    /// let cmudict = CmuDict::new("cmudict.json").unwrap();
+    /// assert_eq!(cmudict.rhyme_verdict("hissed", "mist").unwrap(), Verdict::Yes);
+    /// assert_eq!(cmudict.rhyme_verdict("comfy", "chair").unwrap(), Verdict::No);
+    /// assert_eq!(
+    ///     cmudict.rhyme_verdict("hissed", "Kowalski").unwrap(),
+    ///     Verdict::Unknown { missing: vec!["Kowalski".to_string()] }
+    /// );
+    /// ```
+    pub fn rhyme_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        Ok(self.both(a, b, |a, b| eval_rhyme(a, b, &self.rhyme_options)))
     }
 
     /// Use CMUdict phonetic encoding to determine if two words alliterate.
@@ -101,88 +630,832 @@ impl CmuDict {
     /// assert!(!cmudict.alliteration("lazy", "dog").unwrap());
     /// ```
     pub fn alliteration(&self, a: &str, b: &str) -> Result<bool, Error> {
-        if Word::parse(Rule::vowel_first, a.get(..1).unwrap_or_default()).is_ok() {
-            return Ok(false);
+        Ok(self.alliteration_verdict(a, b)?.is_yes())
+    }
+
+    /// Like `alliteration`, telling words that don't alliterate apart from
+    /// words that aren't in the dictionary.
+    pub fn alliteration_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        Ok(self.both(a, b, eval_alliteration))
+    }
+
+    /// Compare the pronunciations of two words as said in the dialect, or
+    /// report the ones that aren't in the dictionary. Words whose
+    /// pronunciations are derived are reported too.
+    fn both<F>(&self, a: &str, b: &str, compare: F) -> Verdict
+    where
+        F: Fn(Said, Said) -> bool,
+    {
+        match (self.find(a), self.find(b)) {
+            (Some(phones_a), Some(phones_b)) => {
+                let derived = [(a, phones_a.is_derived()), (b, phones_b.is_derived())]
+                    .iter()
+                    .filter(|(_, derived)| *derived)
+                    .map(|(w, _)| w.to_string())
+                    .collect::<Vec<String>>();
+                let verdict = compare(self.said(a, phones_a), self.said(b, phones_b)).into();
+
+                if derived.is_empty() {
+                    verdict
+                } else {
+                    Verdict::Derived {
+                        derived,
+                        verdict: Box::new(verdict),
+                    }
+                }
+            }
+            (phones_a, phones_b) => Verdict::Unknown {
+                missing: [(a, phones_a.is_none()), (b, phones_b.is_none())]
+                    .iter()
+                    .filter(|(_, missing)| *missing)
+                    .map(|(w, _)| w.to_string())
+                    .collect(),
+            },
+        }
+    }
+
+    /// The pronunciations of a word as said in the dialect, as `rhyme` and
+    /// `alliteration` compare them.
+    pub(crate) fn said_pronunciations<'a>(&'a self, w: &'a str) -> Option<Said<'a>> {
+        self.find(w)
+            .map(|pronunciations| self.said(w, pronunciations))
+    }
+
+    /// The rhyming part of a pronunciation as `rhyme` compares it, None
+    /// without a vowel to anchor it.
+    pub(crate) fn rhyme_key(&self, phones: &[Phoneme]) -> Option<RhymeKey> {
+        self.rhyme_options.rhyme_key(phones)
+    }
+
+    /// The pronunciations of a word as said in the dialect, which goes by the
+    /// word as it's looked up.
+    fn said<'a>(&'a self, word: &'a str, pronunciations: Pronunciations<'a>) -> Said<'a> {
+        Said {
+            word: self.normalizer.normalized(word),
+            dialect: &self.dialect,
+            pronunciations,
         }
+    }
+}
+
+/// The pronunciations of a word as said in a dialect, each changed as it's
+/// needed and only borrowed from the dictionary for General American.
+#[derive(Clone)]
+pub(crate) struct Said<'a> {
+    word: Cow<'a, str>,
+    dialect: &'a Dialect,
+    pronunciations: Pronunciations<'a>,
+}
+
+impl<'a> Iterator for Said<'a> {
+    type Item = Cow<'a, [Phoneme]>;
+
+    fn next(&mut self) -> Option<Cow<'a, [Phoneme]>> {
+        let phones = self.pronunciations.next()?;
+        Some(self.dialect.pronounce(&self.word, phones))
+    }
+}
+
+/// Where the default dictionary lives: the path in the `TTAW_CMUDICT`
+/// environment variable, or `cmudict.json` in the data directory, see
+/// `default_data_dir`.
+pub fn default_path() -> PathBuf {
+    if let Some(path) = env::var_os(PATH_VAR).filter(|p| !p.is_empty()) {
+        return PathBuf::from(path);
+    }
+
+    match default_data_dir() {
+        Some(dir) => dir.join(FILE_NAME),
+        None => PathBuf::from(FILE_NAME),
+    }
+}
+
+/// CMUdict phonetic encoding, using the default dictionary.
+pub fn encoding(w: &str) -> Result<Option<Vec<Vec<String>>>, Error> {
+    CmuDict::global()?.encoding(w)
+}
+
+/// Use CMUdict phonetic encoding to determine if two words rhyme, using the
+/// default dictionary.
+///
+/// ```rust,no_run
+/// extern crate ttaw;
+/// use ttaw;
+/// assert!(ttaw::cmu::rhyme("hissed", "mist").unwrap());
+/// ```
+pub fn rhyme(a: &str, b: &str) -> Result<bool, Error> {
+    CmuDict::global()?.rhyme(a, b)
+}
+
+/// Use CMUdict phonetic encoding to determine if two words alliterate, using
+/// the default dictionary.
+pub fn alliteration(a: &str, b: &str) -> Result<bool, Error> {
+    CmuDict::global()?.alliteration(a, b)
+}
+
+/// Number of syllables in a word, using the default dictionary.
+pub fn syllables(w: &str) -> Result<Option<usize>, Error> {
+    CmuDict::global()?.syllables(w)
+}
+
+fn rhyming_part(phones: &[Phoneme], anchor: Anchor) -> Option<&[Phoneme]> {
+    phones
+        .iter()
+        .rposition(|p| match anchor {
+            Anchor::Primary => p.stress() == Some(1),
+            Anchor::Stressed => p.stress() == Some(1) || p.stress() == Some(2),
+            Anchor::Vowel => p.is_vowel(),
+        })
+        .map(|i| &phones[i..])
+}
+
+fn eval_rhyme(phones_a: Said, phones_b: Said, options: &RhymeOptions) -> bool {
+    // pronunciations without a vowel to anchor a rhyme don't rhyme at all,
+    // and each key is worked out once
+    let keys_b = phones_b
+        .filter_map(|p| options.rhyme_key(&p))
+        .collect::<Vec<RhymeKey>>();
 
-        if Word::parse(Rule::vowel_first, b.get(..1).unwrap_or_default()).is_ok() {
-            return Ok(false);
+    phones_a
+        .filter_map(|p| options.rhyme_key(&p))
+        .any(|a| keys_b.contains(&a))
+}
+
+/// Words spelled with a vowel first don't alliterate, whatever they sound
+/// like.
+fn eval_alliteration(phones_a: Said, phones_b: Said) -> bool {
+    for word in &[&phones_a.word, &phones_b.word] {
+        let word = word.trim_start_matches('\'');
+        if Word::parse(Rule::vowel_first, word.get(..1).unwrap_or_default()).is_ok() {
+            return false;
         }
+    }
 
-        if let (Some(phones_a), Some(phones_b)) = (
-            self.dict.get(a.to_string().to_lowercase().trim()),
-            self.dict.get(b.to_string().to_lowercase().trim()),
-        ) {
-            return Ok(eval_alliteration(phones_a, phones_b));
+    for a in phones_a {
+        for b in phones_b.clone() {
+            if let (Some(a), Some(b)) = (a.first(), b.first()) {
+                return a == b;
+            }
         }
+    }
+
+    false
+}
+
+/// How words are cleaned up before they're looked up in the dictionary, the
+/// same for every `CmuDict` method. By default words are trimmed of
+/// whitespace and surrounding punctuation short of the apostrophes next to
+/// them, curly quotes are straightened and words are lowercased. Words that
+/// aren't in the dictionary are looked up without those apostrophes ("'til"
+/// is in it, "'night'" isn't), and then pronounced from their stem ("dog's"
+/// as "dog" + "Z", "baked" as "bake" + "T"). Pronouncing a word from the two
+/// words of a compound ("snowman") is opt-in, see `Normalizer::compounds`.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::cmu::Normalizer;
+///
+/// assert_eq!(Normalizer::new().normalize(" \u{201c}Dog\u{2019}s!\u{201d}"), "dog's");
+/// assert_eq!(Normalizer::new().normalize("\u{2018}Til,"), "'til");
+/// assert_eq!(Normalizer::new().lowercase(false).normalize("Far."), "Far");
+/// assert_eq!(Normalizer::exact().normalize("Far."), "Far.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalizer {
+    lowercase: bool,
+    punctuation: bool,
+    suffixes: bool,
+    compounds: bool,
+}
 
-        Ok(false)
+impl Default for Normalizer {
+    fn default() -> Self {
+        Normalizer {
+            lowercase: true,
+            punctuation: true,
+            suffixes: true,
+            compounds: false,
+        }
     }
 }
 
-fn rhyming_part(phones: &[String]) -> Option<Vec<String>> {
-    for (i, s) in phones.iter().rev().enumerate() {
-        if let Some(num) = s.chars().collect::<Vec<char>>().last() {
-            if *num == '1' || *num == '2' {
-                return phones.get(phones.len() - 1 - i..).map(|v| v.to_vec());
+impl Normalizer {
+    pub fn new() -> Self {
+        Normalizer::default()
+    }
+
+    /// Look words up exactly as given.
+    pub fn exact() -> Self {
+        Normalizer {
+            lowercase: false,
+            punctuation: false,
+            suffixes: false,
+            compounds: false,
+        }
+    }
+
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Whether to trim whitespace and punctuation and straighten curly quotes,
+    /// keeping apostrophes next to the word.
+    pub fn punctuation(mut self, punctuation: bool) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    /// Whether to derive words ending in "-s", "-es", "-'s", "-ed", "-ing",
+    /// "-er", "-ly" or "-ness" from their stem.
+    pub fn suffixes(mut self, suffixes: bool) -> Self {
+        self.suffixes = suffixes;
+        self
+    }
+
+    /// Whether to derive compounds of two words, the second word taking
+    /// secondary stress, off by default. Both parts must be in the dictionary,
+    /// at least three letters long and not function words, but a spelling
+    /// can still split into words it isn't made of ("carpet" as "car" +
+    /// "pet"), giving a word that should be reported unknown a wrong
+    /// pronunciation.
+    pub fn compounds(mut self, compounds: bool) -> Self {
+        self.compounds = compounds;
+        self
+    }
+
+    /// The word as it's looked up, before any suffix is stripped.
+    pub fn normalize(&self, word: &str) -> String {
+        self.normalized(word).into_owned()
+    }
+
+    /// Like `normalize`, borrowing the word when it's already normalized.
+    fn normalized<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        let mut word = Cow::Borrowed(word);
+
+        if self.punctuation {
+            if word.contains(|c| straighten(c) != c) {
+                word = Cow::Owned(word.chars().map(straighten).collect());
             }
+
+            word = match word {
+                Cow::Borrowed(w) => Cow::Borrowed(trim(w)),
+                Cow::Owned(w) => Cow::Owned(trim(&w).to_string()),
+            };
+        }
+
+        if self.lowercase && word.chars().flat_map(char::to_lowercase).ne(word.chars()) {
+            word = Cow::Owned(word.to_lowercase());
         }
+
+        word
+    }
+}
+
+/// Curly quotes and other apostrophes straightened.
+fn straighten(c: char) -> char {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{02bc}' | '`' => '\'',
+        '\u{201c}' | '\u{201d}' => '"',
+        c => c,
     }
+}
+
+/// A word without the punctuation around it, except for apostrophes right
+/// next to it as in "'til" or "goin'".
+fn trim(word: &str) -> &str {
+    let start = match word.find(char::is_alphanumeric) {
+        Some(start) => start,
+        None => return "",
+    };
+    let end = word
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| c.is_alphanumeric())
+        .map_or(start, |(i, c)| i + c.len_utf8());
 
-    None
+    let quoted = |c: &char| *c == '\'';
+    let start = start - word[..start].chars().rev().take_while(quoted).count();
+    let end = end + word[end..].chars().take_while(quoted).count();
+    &word[start..end]
 }
 
-fn eval_rhyme(phones_a: &[Vec<String>], phones_b: &[Vec<String>]) -> bool {
-    for a in phones_a {
-        for b in phones_b {
-            if rhyming_part(a) == rhyming_part(b) {
-                return true;
+/// How strictly `CmuDict::rhyme` compares the rhyming parts of two words,
+/// from the anchoring vowel on. By default the anchor is the last vowel with
+/// primary or secondary stress and the parts must be the same phonemes with
+/// the same stress.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::cmu::{Anchor, RhymeOptions};
+///
+/// let options = RhymeOptions::new()
+///     .reduced_vowels(true)
+///     .anchor(Anchor::Primary);
+/// assert_eq!(options, RhymeOptions::strict().reduced_vowels(true).anchor(Anchor::Primary));
+/// assert_ne!(options, RhymeOptions::loose());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RhymeOptions {
+    ignore_stress: bool,
+    reduced_vowels: bool,
+    family: bool,
+    before_r: bool,
+    anchor: Anchor,
+}
+
+/// The vowels that can start a rhyme, the last one in the word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// Primary stress only, "RE-cord" rhymes from "EH".
+    Primary,
+    /// Primary or secondary stress.
+    Stressed,
+    /// Any vowel, making weak rhymes of unstressed endings like "city" and
+    /// "pretty" in "-ty".
+    Vowel,
+}
+
+impl Default for RhymeOptions {
+    fn default() -> Self {
+        RhymeOptions {
+            ignore_stress: false,
+            reduced_vowels: false,
+            family: false,
+            before_r: false,
+            anchor: Anchor::Stressed,
+        }
+    }
+}
+
+impl RhymeOptions {
+    pub fn new() -> Self {
+        RhymeOptions::default()
+    }
+
+    /// Exact rhymes only, the default.
+    pub fn strict() -> Self {
+        RhymeOptions::default()
+    }
+
+    /// Every switch on, anchored on stressed vowels.
+    pub fn loose() -> Self {
+        RhymeOptions::new()
+            .ignore_stress(true)
+            .reduced_vowels(true)
+            .family(true)
+            .before_r(true)
+    }
+
+    /// Whether to ignore the stress of the vowels after the anchoring one,
+    /// which keeps its own, so "OW2" in "cargo" matches "OW0" in "Margo" when
+    /// the rhyme is anchored on primary stress.
+    pub fn ignore_stress(mut self, ignore_stress: bool) -> Self {
+        self.ignore_stress = ignore_stress;
+        self
+    }
+
+    /// Whether unstressed "AH0", "IH0", "UH0" and "EH0" after the anchoring
+    /// vowel are all the same reduced vowel.
+    pub fn reduced_vowels(mut self, reduced_vowels: bool) -> Self {
+        self.reduced_vowels = reduced_vowels;
+        self
+    }
+
+    /// Whether consonants that differ only in voicing match, making family
+    /// rhymes like "cab" and "cap" or "grease" and "breeze".
+    pub fn family(mut self, family: bool) -> Self {
+        self.family = family;
+        self
+    }
+
+    /// Whether vowels before "R" that most American accents don't tell apart
+    /// match: "IY" and "IH" as in "hear" and "near", "EY" and "EH", "UW" and
+    /// "UH", "OW" and "AO".
+    pub fn before_r(mut self, before_r: bool) -> Self {
+        self.before_r = before_r;
+        self
+    }
+
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// The rhyming part of `phones` as it's compared, None without a vowel to
+    /// anchor it.
+    fn rhyme_key(&self, phones: &[Phoneme]) -> Option<RhymeKey> {
+        rhyming_part(phones, self.anchor).map(|part| RhymeKey {
+            phones: self.key(part),
+            syllables: part.iter().filter(|p| p.is_vowel()).count(),
+        })
+    }
+
+    /// What's compared of a rhyming part: each phoneme's symbol and stress.
+    fn key(&self, part: &[Phoneme]) -> Vec<(&'static str, Option<u8>)> {
+        part.iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let mut base = p.base();
+                let mut stress = p.stress();
+
+                if !p.is_vowel() {
+                    if self.family {
+                        base = voiceless(base);
+                    }
+                    return (base, stress);
+                }
+
+                if self.before_r && part.get(i + 1).is_some_and(|r| r.base() == "R") {
+                    base = match base {
+                        "IY" => "IH",
+                        "EY" => "EH",
+                        "UW" => "UH",
+                        "OW" => "AO",
+                        base => base,
+                    };
+                }
+                let reduced = matches!(base, "AH" | "IH" | "UH" | "EH");
+                if self.reduced_vowels && i > 0 && stress == Some(0) && reduced {
+                    base = "AH";
+                }
+                if self.ignore_stress && i > 0 {
+                    stress = None;
+                }
+
+                (base, stress)
+            })
+            .collect()
+    }
+}
+
+/// The rhyming part of a pronunciation as it's compared under some
+/// `RhymeOptions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RhymeKey {
+    phones: Vec<(&'static str, Option<u8>)>,
+    /// The vowels of the rhyming part.
+    pub(crate) syllables: usize,
+}
+
+impl RhymeKey {
+    /// The symbols without stress, e.g. "IH S T".
+    pub(crate) fn sound(&self) -> String {
+        self.phones
+            .iter()
+            .map(|(base, _)| *base)
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+/// The voiceless consonant of a pair differing only in voicing.
+fn voiceless(base: &'static str) -> &'static str {
+    match base {
+        "B" => "P",
+        "D" => "T",
+        "G" => "K",
+        "V" => "F",
+        "DH" => "TH",
+        "Z" => "S",
+        "ZH" => "SH",
+        "JH" => "CH",
+        base => base,
+    }
+}
+
+/// Where the CMU dictionary is downloaded from, when it isn't on disk yet.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::cmu::Source;
+///
+/// let pinned = Source::revision("7e7b4a4")
+///     .sha256("3f9a1c0e5b2d4f6a8c7e9b1d3f5a7c9e0b2d4f6a8c1e3b5d7f9a2c4e6b8d0f1a");
+/// assert_eq!(
+///     pinned.url(),
+///     "https://raw.githubusercontent.com/cmusphinx/cmudict/7e7b4a4/cmudict.dict"
+/// );
+///
+/// // a local mirror
+/// let mirror = Source::new("file:///srv/mirror/cmudict.dict");
+/// assert_eq!(mirror.checksum(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    url: String,
+    sha256: Option<String>,
+}
+
+/// Environment variable with the URL to download the dictionary from.
+pub const URL_VAR: &str = "TTAW_CMUDICT_URL";
+
+impl Source {
+    /// Download the dictionary from `url`, in the upstream `cmudict.dict`
+    /// format. `http(s)://` and `file://` URLs are supported.
+    pub fn new(url: &str) -> Self {
+        Source {
+            url: url.to_string(),
+            sha256: None,
+        }
+    }
+
+    /// The upstream dictionary at a git revision (commit, tag or branch) of
+    /// the cmusphinx/cmudict repository.
+    pub fn revision(revision: &str) -> Self {
+        Source::new(&format!(
+            "https://raw.githubusercontent.com/cmusphinx/cmudict/{}/cmudict.dict",
+            revision
+        ))
+    }
+
+    /// The URL in the `TTAW_CMUDICT_URL` environment variable if set, the
+    /// upstream `master` otherwise.
+    pub fn from_env() -> Self {
+        match env::var(URL_VAR) {
+            Ok(url) if !url.is_empty() => Source::new(&url),
+            _ => Source::default(),
+        }
+    }
+
+    /// Reject a download unless its SHA-256 digest, in hex, is `sha256`.
+    pub fn sha256(mut self, sha256: &str) -> Self {
+        self.sha256 = Some(sha256.to_lowercase());
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn checksum(&self) -> Option<&str> {
+        self.sha256.as_deref()
+    }
+
+    fn fetch(&self) -> Result<String, Error> {
+        let bytes = match self.url.strip_prefix("file://") {
+            Some(path) => fs::read(path)?,
+            None => reqwest::blocking::get(&self.url)?
+                .error_for_status()?
+                .bytes()?
+                .to_vec(),
+        };
+
+        // the digest is of the file as published, before it's decoded
+        if let Some(expected) = &self.sha256 {
+            let actual = Sha256::digest(&bytes)
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>();
+            if actual != *expected {
+                return Err(Error::ChecksumMismatch {
+                    url: self.url.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
             }
         }
+
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
     }
+}
 
-    false
+impl Default for Source {
+    fn default() -> Self {
+        Source::revision("master")
+    }
 }
 
-fn eval_alliteration(phones_a: &[Vec<String>], phones_b: &[Vec<String>]) -> bool {
-    for a in phones_a {
-        for b in phones_b {
-            if let (Some(a), Some(b)) = (a.first(), b.first()) {
-                return a == b;
+/// Loads a `CmuDict` from the first of these that has a dictionary:
+///
+/// 1. a dictionary embedded in the program, in the serialized JSON format
+/// 2. the cache directory, the data directory `$XDG_DATA_HOME/ttaw` (or
+///    `~/.local/share/ttaw`) by default
+/// 3. an explicit path, to a dictionary file or a directory containing one
+/// 4. a download from the `Source`, saved to the explicit path if there is one
+///    and to the cache directory otherwise
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::cmu::CmuDict;
+/// use ttaw::Error;
+///
+/// let cmudict = CmuDict::builder()
+///     .embedded(r#"{"hissed": [["HH", "IH1", "S", "T"]], "mist": [["M", "IH1", "S", "T"]]}"#)
+///     .build()
+///     .unwrap();
+/// assert!(cmudict.rhyme("hissed", "mist").unwrap());
+///
+/// // nowhere to find a dictionary
+/// assert_eq!(
+///     CmuDict::builder().no_cache().download(false).build().err(),
+///     Some(Error::DictionaryUnavailable)
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CmuDictBuilder {
+    embedded: Option<&'static str>,
+    cache_dir: Option<PathBuf>,
+    path: Option<PathBuf>,
+    source: Source,
+    download: bool,
+    normalizer: Normalizer,
+    dialect: Dialect,
+    rhyme_options: RhymeOptions,
+}
+
+impl Default for CmuDictBuilder {
+    fn default() -> Self {
+        CmuDictBuilder {
+            embedded: None,
+            cache_dir: default_data_dir(),
+            path: None,
+            source: Source::from_env(),
+            download: true,
+            normalizer: Normalizer::default(),
+            dialect: Dialect::default(),
+            rhyme_options: RhymeOptions::default(),
+        }
+    }
+}
+
+impl CmuDictBuilder {
+    /// A serialized dictionary compiled into the program, e.g. with
+    /// `include_str!`.
+    pub fn embedded(mut self, json: &'static str) -> Self {
+        self.embedded = Some(json);
+        self
+    }
+
+    pub fn cache_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Don't read from or download to a cache directory.
+    pub fn no_cache(mut self) -> Self {
+        self.cache_dir = None;
+        self
+    }
+
+    /// A dictionary file or a directory containing `cmudict.json`.
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Whether to download the dictionary when it isn't found, on by default.
+    pub fn download(mut self, download: bool) -> Self {
+        self.download = download;
+        self
+    }
+
+    pub fn normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn rhyme_options(mut self, options: RhymeOptions) -> Self {
+        self.rhyme_options = options;
+        self
+    }
+
+    pub fn build(self) -> Result<CmuDict, Error> {
+        let normalizer = self.normalizer.clone();
+        let dialect = self.dialect.clone();
+        let options = self.rhyme_options.clone();
+        self.load().map(|cmudict| {
+            cmudict
+                .with_normalizer(normalizer)
+                .with_dialect(dialect)
+                .with_rhyme_options(options)
+        })
+    }
+
+    fn load(self) -> Result<CmuDict, Error> {
+        if let Some(json) = self.embedded {
+            return Ok(CmuDict {
+                dict: Arc::new(Dictionary::from_json(json, "embedded data")?),
+                normalizer: Normalizer::default(),
+                dialect: Dialect::default(),
+                rhyme_options: RhymeOptions::default(),
+            });
+        }
+
+        let cached = self.cache_dir.as_ref().map(|dir| dir.join(FILE_NAME));
+        let path = self.path.as_ref().map(|path| dictionary_file(path));
+
+        for file in cached.iter().chain(path.iter()) {
+            if file.is_file() {
+                return CmuDict::from_source(file, &self.source);
             }
         }
+
+        let target = match (path, self.cache_dir) {
+            (Some(path), _) if self.download => {
+                if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                    fs::create_dir_all(dir)?;
+                }
+                path
+            }
+            (None, Some(dir)) if self.download => {
+                fs::create_dir_all(&dir)?;
+                dir.join(FILE_NAME)
+            }
+            _ => return Err(Error::DictionaryUnavailable),
+        };
+
+        CmuDict::from_source(target, &self.source)
     }
+}
 
-    false
+/// File name of the serialized dictionary in a directory.
+const FILE_NAME: &str = "cmudict.json";
+
+/// The data directory for the dictionary, `$XDG_DATA_HOME/ttaw`, falling back
+/// to `~/.local/share/ttaw`.
+pub fn default_data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .map(|dir| dir.join("ttaw"))
+}
+
+/// The dictionary file at `path`, `path` itself or `cmudict.json` in it for a
+/// directory.
+fn dictionary_file(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join(FILE_NAME)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn corrupt<O: fmt::Display>(origin: O, err: Error) -> Error {
+    let line = match err {
+        Error::Json(ref e) => Some(e.line()),
+        _ => None,
+    };
+
+    Error::CorruptDictionary {
+        origin: origin.to_string(),
+        line,
+        source: Box::new(err),
+    }
 }
 
-fn from_json_file(path: &Path) -> Result<HashMap<String, Vec<Vec<String>>>, Error> {
+fn from_json_file(path: &Path, source: &Source) -> Result<Dictionary, Error> {
+    let path = &dictionary_file(path);
+
     if !path.exists() {
         // regenerate if the file isn't there
-        if path.is_dir() {
-            download_and_serialize(&path.join("cmudict.json"))?;
-        } else {
-            download_and_serialize(path)?;
-        }
+        download_and_serialize_from(source, path)?;
     }
 
     let dict_json = fs::read_to_string(path)?;
-    let dict: HashMap<String, Vec<Vec<String>>> = serde_json::from_str(&dict_json)?;
-    Ok(dict)
+    Dictionary::from_json(&dict_json, path.display())
 }
 
 pub fn download_and_serialize(path: &Path) -> Result<(), Error> {
-    let dict_string = reqwest::blocking::get(
-        "https://raw.githubusercontent.com/cmusphinx/cmudict/master/cmudict.dict",
-    )?
-    .text()?;
+    download_and_serialize_from(&Source::from_env(), path)
+}
+
+/// Download the dictionary from `source` and serialize it to `path`. The
+/// dictionary is written to a temporary file first and renamed into place, so
+/// a failed download never leaves a partial dictionary behind.
+pub fn download_and_serialize_from(source: &Source, path: &Path) -> Result<(), Error> {
+    let dict_string = source.fetch()?;
 
     let cursor = io::Cursor::new(dict_string);
     let lines = cursor.lines().collect::<Result<Vec<_>, _>>()?;
 
     let mut dict: HashMap<String, Vec<Vec<String>>> = HashMap::new();
 
-    for line in lines {
+    for (n, line) in lines.iter().enumerate() {
+        // entries can end in a comment, e.g. "# foreign"
+        let line = line.split('#').next().unwrap_or_default();
+
+        for phoneme in line.split_whitespace().skip(1) {
+            if let Err(e) = phoneme.parse::<Phoneme>() {
+                return Err(Error::CorruptDictionary {
+                    origin: source.url().to_string(),
+                    line: Some(n + 1),
+                    source: Box::new(e),
+                });
+            }
+        }
+
         let entry = line
             .split_whitespace()
             .map(|s| s.to_string())
@@ -202,9 +1475,11 @@ pub fn download_and_serialize(path: &Path) -> Result<(), Error> {
         }
     }
 
-    let serialized = serde_json::to_string(&dict)?;
-    fs::write(path, serialized)?;
-    Ok(())
+    atomic::write(path, |file| {
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &dict)?;
+        Ok(writer.flush()?)
+    })
 }
 
 #[cfg(test)]
@@ -219,11 +1494,25 @@ mod tests {
         assert!(dict.is_ok());
     }
 
+    #[test]
+    fn normalized_borrows() {
+        let normalizer = Normalizer::new();
+        assert!(matches!(
+            normalizer.normalized("dog's"),
+            Cow::Borrowed("dog's")
+        ));
+        assert!(matches!(
+            normalizer.normalized("(dog)"),
+            Cow::Borrowed("dog")
+        ));
+        assert_eq!(normalizer.normalized("Dog\u{2019}s"), "dog's");
+    }
+
     #[test]
     fn test_from_json_file() {
         let dir = tempfile::tempdir().unwrap();
         let fpath = dir.path().join("serialized");
-        let dict = from_json_file(&fpath);
+        let dict = from_json_file(&fpath, &Source::default());
         assert!(dict.is_ok());
     }
 }
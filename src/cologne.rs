@@ -0,0 +1,73 @@
+/// Cologne phonetics (Kölner Phonetik), a Soundex-like digit code tuned for
+/// German names. Unlike Soundex the code isn't truncated or padded.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::cologne::encoding("Müller-Lüdenscheidt"), "65752682");
+/// assert_eq!(ttaw::cologne::encoding("Meyer"), ttaw::cologne::encoding("Maier"));
+/// ```
+pub fn encoding(input: &str) -> String {
+    let chars = input
+        .to_uppercase()
+        .chars()
+        .filter_map(|c| match c {
+            'Ä' => Some('A'),
+            'Ö' => Some('O'),
+            'Ü' => Some('U'),
+            // ß is already SS after uppercasing, only the capital survives
+            'ẞ' => Some('S'),
+            c if c.is_ascii_uppercase() => Some(c),
+            _ => None,
+        })
+        .collect::<Vec<char>>();
+
+    let mut code = String::new();
+    // the previous code, `None` for letters that aren't coded (H)
+    let mut last = None;
+
+    for (pos, c) in chars.iter().enumerate() {
+        let prev = if pos > 0 { chars.get(pos - 1) } else { None };
+        let next = chars.get(pos + 1);
+
+        for d in digits(*c, prev.cloned(), next.cloned(), pos == 0) {
+            // zeros (vowels) are only kept at the start
+            if Some(d) != last && (d != '0' || code.is_empty()) {
+                code.push(d);
+            }
+            last = Some(d);
+        }
+
+        if *c == 'H' {
+            last = None;
+        }
+    }
+
+    code
+}
+
+fn digits(c: char, prev: Option<char>, next: Option<char>, initial: bool) -> Vec<char> {
+    let next_in = |set: &str| next.is_some_and(|n| set.contains(n));
+    let prev_in = |set: &str| prev.is_some_and(|p| set.contains(p));
+
+    match c {
+        'A' | 'E' | 'I' | 'J' | 'O' | 'U' | 'Y' => vec!['0'],
+        'B' => vec!['1'],
+        'P' if next == Some('H') => vec!['3'],
+        'P' => vec!['1'],
+        'D' | 'T' if next_in("CSZ") => vec!['8'],
+        'D' | 'T' => vec!['2'],
+        'F' | 'V' | 'W' => vec!['3'],
+        'G' | 'K' | 'Q' => vec!['4'],
+        'C' if initial && next_in("AHKLOQRUX") => vec!['4'],
+        'C' if !initial && next_in("AHKOQUX") && !prev_in("SZ") => vec!['4'],
+        'C' => vec!['8'],
+        'X' if prev_in("CKQ") => vec!['8'],
+        'X' => vec!['4', '8'],
+        'L' => vec!['5'],
+        'M' | 'N' => vec!['6'],
+        'R' => vec!['7'],
+        'S' | 'Z' => vec!['8'],
+        _ => vec![],
+    }
+}
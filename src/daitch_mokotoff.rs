@@ -0,0 +1,256 @@
+use encoder::PhoneticCodes;
+
+/// Daitch-Mokotoff Soundex phonetic encoding, six digit codes tuned for Slavic
+/// and Yiddish surnames. Letters that can be read more than one way (e.g. "ch"
+/// as in "chai" or "chin") branch, so a name comes back with every code it
+/// could have.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::daitch_mokotoff::encoding("Moskowitz").to_vec(), vec!["645740"]);
+/// assert_eq!(
+///     ttaw::daitch_mokotoff::encoding("Peters").to_vec(),
+///     vec!["739400", "734000"]
+/// );
+/// ```
+pub fn encoding(input: &str) -> PhoneticCodes {
+    let chars = input
+        .to_lowercase()
+        .chars()
+        .filter_map(fold)
+        .collect::<Vec<char>>();
+
+    if chars.is_empty() {
+        return PhoneticCodes::default();
+    }
+
+    let mut branches = vec![Branch::default()];
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let (pattern, at_start, before_vowel, otherwise) = match RULES
+            .iter()
+            .filter(|rule| matches_at(&chars, pos, rule.0))
+            .max_by_key(|rule| rule.0.chars().count())
+        {
+            Some(rule) => *rule,
+            // letters outside the table aren't coded
+            None => {
+                pos += 1;
+                continue;
+            }
+        };
+
+        let len = pattern.chars().count();
+        let replacements = if pos == 0 {
+            at_start
+        } else if chars.get(pos + len).is_some_and(|c| is_vowel(*c)) {
+            before_vowel
+        } else {
+            otherwise
+        };
+
+        let mut next: Vec<Branch> = vec![];
+        for branch in &branches {
+            for replacement in replacements.split('|') {
+                let mut branch = branch.clone();
+                branch.push(replacement);
+                if !next.contains(&branch) {
+                    next.push(branch);
+                }
+            }
+        }
+        branches = next;
+
+        pos += len;
+    }
+
+    PhoneticCodes::new(branches.into_iter().map(|mut branch| {
+        branch.code.push_str("000000");
+        branch.code.truncate(CODE_LENGTH);
+        branch.code
+    }))
+}
+
+const CODE_LENGTH: usize = 6;
+
+/// One reading of a name, branches are created for each alternative code.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Branch {
+    code: String,
+    /// Code of the last letters, codes repeated by adjacent letters are only
+    /// added once. Uncoded letters (e.g. vowels) separate repeats.
+    last: String,
+}
+
+impl Branch {
+    fn push(&mut self, replacement: &str) {
+        if !self.last.ends_with(replacement) && self.code.len() < CODE_LENGTH {
+            self.code.push_str(replacement);
+        }
+        self.last = replacement.to_string();
+    }
+}
+
+fn matches_at(chars: &[char], pos: usize, pattern: &str) -> bool {
+    pattern
+        .chars()
+        .enumerate()
+        .all(|(i, c)| chars.get(pos + i) == Some(&c))
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Fold accented letters to the letters in the code table, drop anything else.
+fn fold(c: char) -> Option<char> {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => Some('a'),
+        'è' | 'é' | 'ê' | 'ë' => Some('e'),
+        'ì' | 'í' | 'î' | 'ï' => Some('i'),
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => Some('o'),
+        'ù' | 'ú' | 'û' | 'ü' => Some('u'),
+        'ç' | 'ć' | 'č' => Some('c'),
+        'ł' => Some('l'),
+        'ń' | 'ñ' => Some('n'),
+        'ś' | 'š' => Some('s'),
+        'ź' | 'ż' | 'ž' => Some('z'),
+        'ß' => Some('s'),
+        'ą' | 'ę' | 'ţ' => Some(c),
+        c if c.is_ascii_lowercase() => Some(c),
+        _ => None,
+    }
+}
+
+/// The Daitch-Mokotoff code table:
+/// (letters, code at the start of a name, code before a vowel, any other code).
+/// Alternative codes are separated by '|', an empty code means not coded.
+const RULES: &[(&str, &str, &str, &str)] = &[
+    // vowels
+    ("a", "0", "", ""),
+    ("ai", "0", "1", ""),
+    ("aj", "0", "1", ""),
+    ("ay", "0", "1", ""),
+    ("au", "0", "7", ""),
+    ("ą", "", "", "6|"),
+    ("e", "0", "", ""),
+    ("ei", "0", "1", ""),
+    ("ej", "0", "1", ""),
+    ("ey", "0", "1", ""),
+    ("eu", "1", "1", ""),
+    ("ę", "", "", "6|"),
+    ("i", "0", "", ""),
+    ("ia", "1", "", ""),
+    ("ie", "1", "", ""),
+    ("io", "1", "", ""),
+    ("iu", "1", "", ""),
+    ("o", "0", "", ""),
+    ("oi", "0", "1", ""),
+    ("oj", "0", "1", ""),
+    ("oy", "0", "1", ""),
+    ("u", "0", "", ""),
+    ("ue", "0", "", ""),
+    ("ui", "0", "1", ""),
+    ("uj", "0", "1", ""),
+    ("uy", "0", "1", ""),
+    ("y", "1", "", ""),
+    // consonants
+    ("b", "7", "7", "7"),
+    ("c", "5|4", "5|4", "5|4"),
+    ("ch", "5|4", "5|4", "5|4"),
+    ("chs", "5", "54", "54"),
+    ("ck", "5|45", "5|45", "5|45"),
+    ("cs", "4", "4", "4"),
+    ("csz", "4", "4", "4"),
+    ("cz", "4", "4", "4"),
+    ("czs", "4", "4", "4"),
+    ("d", "3", "3", "3"),
+    ("drs", "4", "4", "4"),
+    ("drz", "4", "4", "4"),
+    ("ds", "4", "4", "4"),
+    ("dsh", "4", "4", "4"),
+    ("dsz", "4", "4", "4"),
+    ("dt", "3", "3", "3"),
+    ("dz", "4", "4", "4"),
+    ("dzh", "4", "4", "4"),
+    ("dzs", "4", "4", "4"),
+    ("f", "7", "7", "7"),
+    ("fb", "7", "7", "7"),
+    ("g", "5", "5", "5"),
+    ("h", "5", "5", ""),
+    ("j", "1|4", "|4", "|4"),
+    ("k", "5", "5", "5"),
+    ("kh", "5", "5", "5"),
+    ("ks", "5", "54", "54"),
+    ("l", "8", "8", "8"),
+    ("m", "6", "6", "6"),
+    ("mn", "66", "66", "66"),
+    ("n", "6", "6", "6"),
+    ("nm", "66", "66", "66"),
+    ("p", "7", "7", "7"),
+    ("pf", "7", "7", "7"),
+    ("ph", "7", "7", "7"),
+    ("q", "5", "5", "5"),
+    ("r", "9", "9", "9"),
+    ("rs", "94|4", "94|4", "94|4"),
+    ("rz", "94|4", "94|4", "94|4"),
+    ("s", "4", "4", "4"),
+    ("sc", "2", "4", "4"),
+    ("sch", "4", "4", "4"),
+    ("schd", "2", "43", "43"),
+    ("scht", "2", "43", "43"),
+    ("schtch", "2", "4", "4"),
+    ("schtsch", "2", "4", "4"),
+    ("schtsh", "2", "4", "4"),
+    ("sd", "2", "43", "43"),
+    ("sh", "4", "4", "4"),
+    ("shch", "2", "4", "4"),
+    ("shd", "2", "43", "43"),
+    ("sht", "2", "43", "43"),
+    ("shtch", "2", "4", "4"),
+    ("shtsh", "2", "4", "4"),
+    ("st", "2", "43", "43"),
+    ("stch", "2", "4", "4"),
+    ("strs", "2", "4", "4"),
+    ("strz", "2", "4", "4"),
+    ("stsch", "2", "4", "4"),
+    ("stsh", "2", "4", "4"),
+    ("sz", "4", "4", "4"),
+    ("szcs", "2", "4", "4"),
+    ("szcz", "2", "4", "4"),
+    ("szd", "2", "43", "43"),
+    ("szt", "2", "43", "43"),
+    ("t", "3", "3", "3"),
+    ("ţ", "3|4", "3|4", "3|4"),
+    ("tc", "4", "4", "4"),
+    ("tch", "4", "4", "4"),
+    ("th", "3", "3", "3"),
+    ("trs", "4", "4", "4"),
+    ("trz", "4", "4", "4"),
+    ("ts", "4", "4", "4"),
+    ("tsch", "4", "4", "4"),
+    ("tsh", "4", "4", "4"),
+    ("tsz", "4", "4", "4"),
+    ("ttch", "4", "4", "4"),
+    ("tts", "4", "4", "4"),
+    ("ttsch", "4", "4", "4"),
+    ("ttsz", "4", "4", "4"),
+    ("ttz", "4", "4", "4"),
+    ("tz", "4", "4", "4"),
+    ("tzs", "4", "4", "4"),
+    ("v", "7", "7", "7"),
+    ("w", "7", "7", "7"),
+    ("x", "5", "54", "54"),
+    ("z", "4", "4", "4"),
+    ("zd", "2", "43", "43"),
+    ("zdz", "2", "4", "4"),
+    ("zdzh", "2", "4", "4"),
+    ("zh", "4", "4", "4"),
+    ("zhd", "2", "43", "43"),
+    ("zhdzh", "2", "4", "4"),
+    ("zs", "4", "4", "4"),
+    ("zsch", "4", "4", "4"),
+    ("zsh", "4", "4", "4"),
+];
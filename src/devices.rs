@@ -0,0 +1,335 @@
+use cmu::CmuDict;
+use metaphone;
+use std::ops::Range;
+use text::{self, is_function_word, Token};
+
+/// A repeated sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Device {
+    /// The same initial consonant.
+    Alliteration,
+    /// The same stressed vowel.
+    Assonance,
+    /// The same final consonant.
+    Consonance,
+}
+
+/// Words close together sharing a sound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub device: Device,
+    /// The shared sound as an ARPAbet symbol without stress, e.g. "B" or "IY".
+    /// Double Metaphone doesn't tell voiced and voiceless consonants apart,
+    /// so for a run of words the dictionary doesn't know it's the voiceless
+    /// one, "P" for both "B" and "P". For assonance between such words it's
+    /// the spelled vowels, e.g. "ee", which match the dictionary's vowels
+    /// they can be said as.
+    pub sound: String,
+    /// The words sharing the sound, function words in between left out.
+    pub words: Vec<Token>,
+}
+
+impl Run {
+    /// Byte range of the run in the text, from its first word to its last.
+    pub fn span(&self) -> Range<usize> {
+        let start = self.words.first().map_or(0, |w| w.span.start);
+        let end = self.words.last().map_or(0, |w| w.span.end);
+        start..end
+    }
+}
+
+/// Finds runs of alliteration, assonance and consonance in running text.
+///
+/// Words are pronounced with the CMU dictionary when it's given and knows
+/// them, as said in the dictionary's dialect, and with Double Metaphone
+/// otherwise. Up to `gap` function words such
+/// as "a" or "the" may come between the words of a run, any other word ends
+/// it, as does the end of a sentence.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::devices::Finder;
+///
+/// let text = "The big brown bear, and a bouncing ball.";
+/// let runs = Finder::new().alliteration(text);
+///
+/// assert_eq!(runs.len(), 1);
+/// // Double Metaphone hears "B" as "P"
+/// assert_eq!(runs[0].sound, "P");
+/// assert_eq!(runs[0].words.len(), 5);
+/// assert_eq!(&text[runs[0].span()], "big brown bear, and a bouncing ball");
+/// ```
+#[derive(Clone)]
+pub struct Finder {
+    cmudict: Option<CmuDict>,
+    gap: usize,
+}
+
+impl Default for Finder {
+    fn default() -> Self {
+        Finder {
+            cmudict: None,
+            gap: 2,
+        }
+    }
+}
+
+impl Finder {
+    /// A finder using Double Metaphone alone.
+    pub fn new() -> Self {
+        Finder::default()
+    }
+
+    /// Pronounce words with `cmudict`, Double Metaphone only for the words it
+    /// doesn't know.
+    pub fn cmudict(mut self, cmudict: CmuDict) -> Self {
+        self.cmudict = Some(cmudict);
+        self
+    }
+
+    /// Most function words between two words of a run, 2 by default.
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn alliteration(&self, text: &str) -> Vec<Run> {
+        self.find(text, Device::Alliteration)
+    }
+
+    pub fn assonance(&self, text: &str) -> Vec<Run> {
+        self.find(text, Device::Assonance)
+    }
+
+    pub fn consonance(&self, text: &str) -> Vec<Run> {
+        self.find(text, Device::Consonance)
+    }
+
+    /// Runs of every device, in the order they start.
+    pub fn find_all(&self, text: &str) -> Vec<Run> {
+        let mut runs = self.alliteration(text);
+        runs.extend(self.assonance(text));
+        runs.extend(self.consonance(text));
+        runs.sort_by_key(|run| run.span().start);
+        runs
+    }
+
+    fn find(&self, text: &str, device: Device) -> Vec<Run> {
+        let mut runs = vec![];
+        // the run so far and its sound
+        let mut run: Option<(Run, Sound)> = None;
+        let mut gap = 0;
+
+        for token in text::tokenize(text) {
+            if is_function_word(&token.word) {
+                gap += 1;
+                continue;
+            }
+
+            let sound = self.sound(&token.word, device);
+            let extends = match (&run, &sound) {
+                (Some((run, run_sound)), Some(sound)) => {
+                    gap <= self.gap
+                        && run_sound.matches(sound)
+                        && run.words.last().map(|w| w.sentence) == Some(token.sentence)
+                }
+                _ => false,
+            };
+
+            match (run.as_mut(), sound) {
+                (Some((run, run_sound)), Some(sound)) if extends => {
+                    run.words.push(token);
+                    if sound.exact && !run_sound.exact {
+                        run.sound = sound.symbol.clone();
+                        *run_sound = sound;
+                    }
+                }
+                (_, sound) => {
+                    runs.extend(run.take().map(|(run, _)| run));
+                    run = sound.map(|sound| {
+                        let run = Run {
+                            device,
+                            sound: sound.symbol.clone(),
+                            words: vec![token],
+                        };
+                        (run, sound)
+                    });
+                }
+            }
+
+            gap = 0;
+        }
+
+        runs.extend(run.map(|(run, _)| run));
+        runs.retain(|run| run.words.len() > 1);
+        runs
+    }
+
+    /// The sound of a word that a run of `device` repeats, if it has one.
+    fn sound(&self, word: &str, device: Device) -> Option<Sound> {
+        let pronunciation = self
+            .cmudict
+            .as_ref()
+            .and_then(|cmudict| cmudict.said_pronunciations(word))
+            .and_then(|mut pronunciations| pronunciations.next());
+
+        if let Some(phones) = pronunciation {
+            let phone = match device {
+                Device::Alliteration => phones.first().filter(|p| !p.is_vowel()),
+                Device::Assonance => phones.iter().find(|p| p.stress() == Some(1)),
+                Device::Consonance => phones.last().filter(|p| !p.is_vowel()),
+            };
+            return phone.map(|p| Sound {
+                symbol: p.base().to_string(),
+                exact: true,
+            });
+        }
+
+        let word = word.to_lowercase();
+        let code = metaphone::encoding(&word).primary;
+        let symbol = match device {
+            // Double Metaphone writes initial "w" and "y" as vowels
+            Device::Alliteration if word.starts_with("wh") => Some("W".to_string()),
+            Device::Alliteration if word.starts_with('w') || word.starts_with('y') => {
+                let next = word.chars().nth(1).unwrap_or_default();
+                if "aeiou".contains(next) {
+                    Some(word[..1].to_uppercase())
+                } else {
+                    code.chars().next().and_then(arpabet)
+                }
+            }
+            Device::Alliteration => code.chars().next().and_then(arpabet),
+            Device::Assonance => spelled_vowels(&word),
+            Device::Consonance if word.ends_with(|c| "aiouy".contains(c)) => None,
+            Device::Consonance => code.chars().last().and_then(arpabet),
+        };
+
+        symbol.map(|symbol| Sound {
+            symbol,
+            exact: false,
+        })
+    }
+}
+
+/// The sound of a word, exact when it's from the dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Sound {
+    symbol: String,
+    exact: bool,
+}
+
+impl Sound {
+    /// Exact sounds match the same sound, others the same class of sound.
+    /// Spelled vowels match the vowels they can be said as.
+    fn matches(&self, other: &Sound) -> bool {
+        match (self.exact, other.exact) {
+            (true, true) => self.symbol == other.symbol,
+            (true, false) if is_spelled(&other.symbol) => {
+                said_as(&other.symbol).contains(&self.symbol.as_str())
+            }
+            (false, true) if is_spelled(&self.symbol) => {
+                said_as(&self.symbol).contains(&other.symbol.as_str())
+            }
+            _ => class(&self.symbol) == class(&other.symbol),
+        }
+    }
+}
+
+/// Whether a sound is spelled vowels rather than an ARPAbet symbol.
+fn is_spelled(symbol: &str) -> bool {
+    symbol.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+/// The ARPAbet vowels spelled vowels are commonly said as when stressed.
+fn said_as(spelled: &str) -> &'static [&'static str] {
+    match spelled {
+        "ai" => &["EY"],
+        "au" => &["AO", "AA"],
+        "ea" => &["IY", "EH", "EY"],
+        "ee" => &["IY"],
+        "ei" => &["EY", "IY", "AY"],
+        "eu" | "ue" => &["UW"],
+        "ie" => &["IY", "AY"],
+        "oa" => &["OW"],
+        "oi" => &["OY"],
+        "oo" => &["UW", "UH"],
+        "ou" => &["AW", "UW", "AH", "OW"],
+        _ => match spelled.chars().next() {
+            Some('a') => &["AE", "EY", "AA", "AO"],
+            Some('e') => &["EH", "IY", "ER"],
+            Some('i') => &["IH", "AY", "ER"],
+            Some('o') => &["AA", "OW", "AO", "AH"],
+            Some('u') => &["AH", "UW", "UH", "ER"],
+            _ => &[],
+        },
+    }
+}
+
+/// The class of an ARPAbet consonant Double Metaphone can tell, voiced and
+/// voiceless consonants together.
+fn class(symbol: &str) -> &str {
+    match symbol {
+        "B" | "P" => "P",
+        "D" | "T" => "T",
+        "G" | "K" => "K",
+        "V" | "F" => "F",
+        "Z" | "S" => "S",
+        "DH" | "TH" => "TH",
+        "ZH" | "SH" | "CH" => "SH",
+        symbol => symbol,
+    }
+}
+
+/// The ARPAbet consonant closest to a Double Metaphone code letter.
+fn arpabet(code: char) -> Option<String> {
+    let symbol = match code {
+        'X' => "SH",
+        '0' => "TH",
+        'H' => "HH",
+        'J' => "JH",
+        'A' => return None,
+        c if c.is_ascii_uppercase() => return Some(c.to_string()),
+        _ => return None,
+    };
+    Some(symbol.to_string())
+}
+
+/// The first group of vowel letters of a word, past any initial consonants.
+fn spelled_vowels(word: &str) -> Option<String> {
+    let vowels = word
+        .chars()
+        .skip_while(|c| !"aeiou".contains(*c))
+        .take_while(|c| "aeiou".contains(*c))
+        .collect::<String>();
+
+    if vowels.is_empty() {
+        None
+    } else {
+        Some(vowels)
+    }
+}
+
+/// Runs of alliteration in a text, using Double Metaphone. See `Finder` to
+/// use the CMU dictionary or change the gap.
+///
+/// ```rust
+/// extern crate ttaw;
+/// let runs = ttaw::devices::find_alliteration("Peter Piper picked a peck of pickled peppers");
+/// assert_eq!(runs.len(), 1);
+/// assert_eq!(runs[0].words.len(), 6);
+/// ```
+pub fn find_alliteration(text: &str) -> Vec<Run> {
+    Finder::new().alliteration(text)
+}
+
+/// Runs of assonance in a text, using the spelling of the vowels. See `Finder`
+/// to use the CMU dictionary.
+pub fn find_assonance(text: &str) -> Vec<Run> {
+    Finder::new().assonance(text)
+}
+
+/// Runs of consonance in a text, using Double Metaphone. See `Finder` to use
+/// the CMU dictionary.
+pub fn find_consonance(text: &str) -> Vec<Run> {
+    Finder::new().consonance(text)
+}
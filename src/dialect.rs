@@ -0,0 +1,293 @@
+use phoneme::Phoneme;
+use std::borrow::Cow;
+
+/// How an accent differs from the General American of CMUdict, applied to
+/// pronunciations before `CmuDict` compares them for rhymes and alliteration.
+/// The transforms go by the sounds around each vowel, and the father-bother
+/// split by a list of words too, so they're approximations: "gas" gets the
+/// vowel of "bath" just like "grass".
+///
+/// ```rust,no_run
+/// extern crate ttaw;
+/// use ttaw::cmu::CmuDict;
+/// use ttaw::dialect::Dialect;
+///
+/// let cmudict = CmuDict::new("cmudict.json").unwrap();
+/// assert!(cmudict.rhyme("father", "bother").unwrap());
+/// assert!(!cmudict.rhyme("father", "farther").unwrap());
+///
+/// let british = cmudict.clone().with_dialect(Dialect::british());
+/// assert!(!british.rhyme("father", "bother").unwrap());
+/// assert!(british.rhyme("father", "farther").unwrap());
+///
+/// let canadian = cmudict.with_dialect(Dialect::canadian());
+/// assert!(canadian.rhyme("caught", "cot").unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dialect {
+    non_rhotic: bool,
+    cot_caught: bool,
+    pin_pen: bool,
+    trap_bath: bool,
+    trap_bath_nasals: bool,
+    mary_marry_merry: bool,
+    father_bother: bool,
+}
+
+impl Dialect {
+    /// General American, CMUdict as it is.
+    pub fn new() -> Self {
+        Dialect::default()
+    }
+
+    pub fn general_american() -> Self {
+        Dialect::new()
+    }
+
+    /// Received Pronunciation: non-rhotic, with the trap-bath split before
+    /// fricatives and nasals and the father-bother split.
+    pub fn british() -> Self {
+        Dialect::new()
+            .non_rhotic(true)
+            .trap_bath(true)
+            .trap_bath_nasals(true)
+            .father_bother(true)
+    }
+
+    /// Like British English, except that the trap-bath split doesn't reach
+    /// the nasals: "bath" has the vowel of "father" but "dance" keeps that of
+    /// "trap".
+    pub fn australian() -> Self {
+        Dialect::british().trap_bath_nasals(false)
+    }
+
+    /// The cot-caught and Mary-marry-merry mergers.
+    pub fn canadian() -> Self {
+        Dialect::new().cot_caught(true).mary_marry_merry(true)
+    }
+
+    /// The pin-pen and Mary-marry-merry mergers.
+    pub fn southern_american() -> Self {
+        Dialect::new().pin_pen(true).mary_marry_merry(true)
+    }
+
+    /// Whether "r" is dropped where no vowel follows, "car" said like "ca"
+    /// and "father" like "farther", with a schwa after other vowels, "near"
+    /// said like "nee-uh".
+    pub fn non_rhotic(mut self, non_rhotic: bool) -> Self {
+        self.non_rhotic = non_rhotic;
+        self
+    }
+
+    /// Whether "caught" is said like "cot", "AO" merged into "AA" except
+    /// before "R", so "horse" doesn't rhyme with "farce".
+    pub fn cot_caught(mut self, cot_caught: bool) -> Self {
+        self.cot_caught = cot_caught;
+        self
+    }
+
+    /// Whether "pen" is said like "pin", "EH" before "N", "M" or "NG" raised to
+    /// "IH".
+    pub fn pin_pen(mut self, pin_pen: bool) -> Self {
+        self.pin_pen = pin_pen;
+        self
+    }
+
+    /// Whether "bath" has the vowel of "father", "AE" before "F", "TH" or "S"
+    /// and a consonant becoming "AA".
+    pub fn trap_bath(mut self, trap_bath: bool) -> Self {
+        self.trap_bath = trap_bath;
+        self
+    }
+
+    /// Whether the trap-bath split also takes "AE" before "N" and "T", "S" or
+    /// "CH", "dance" and "plant".
+    pub fn trap_bath_nasals(mut self, trap_bath_nasals: bool) -> Self {
+        self.trap_bath_nasals = trap_bath_nasals;
+        self
+    }
+
+    /// Whether "Mary", "marry" and "merry" are said alike, "AE" and "EY"
+    /// before "R" becoming "EH".
+    pub fn mary_marry_merry(mut self, mary_marry_merry: bool) -> Self {
+        self.mary_marry_merry = mary_marry_merry;
+        self
+    }
+
+    /// Whether "bother" has the rounded vowel of "lot", "OH", rather than the
+    /// "AA" of "father". Every "AA" becomes "OH" except before an "R" with no
+    /// vowel after it ("car", "hearth") and in the few words with the vowel of
+    /// "palm" ("father", "spa", "drama"), which their derived forms and
+    /// compounds share ("fathers", "grandfather"). A compound of both
+    /// ("godfather") gets the vowel of "palm" throughout.
+    pub fn father_bother(mut self, father_bother: bool) -> Self {
+        self.father_bother = father_bother;
+        self
+    }
+
+    /// The CMUdict pronunciation of `word` as said in the dialect.
+    ///
+    /// ```rust
+    /// extern crate ttaw;
+    /// use ttaw::dialect::Dialect;
+    /// use ttaw::phoneme::Phoneme;
+    ///
+    /// let phones = |s: &str| {
+    ///     s.split(' ')
+    ///         .map(|p| p.parse().unwrap())
+    ///         .collect::<Vec<Phoneme>>()
+    /// };
+    /// let british = Dialect::british();
+    /// assert_eq!(british.apply("car", &phones("K AA1 R")), phones("K AA1"));
+    /// assert_eq!(british.apply("bath", &phones("B AE1 TH")), phones("B AA1 TH"));
+    /// assert_eq!(british.apply("hot", &phones("HH AA1 T"))[1].as_str(), "OH1");
+    /// ```
+    pub fn apply(&self, word: &str, phones: &[Phoneme]) -> Vec<Phoneme> {
+        self.pronounce(word, Cow::Borrowed(phones)).into_owned()
+    }
+
+    /// Like `apply`, leaving the pronunciation borrowed when there's nothing
+    /// to change.
+    pub(crate) fn pronounce<'a>(
+        &self,
+        word: &str,
+        phones: Cow<'a, [Phoneme]>,
+    ) -> Cow<'a, [Phoneme]> {
+        if *self == Dialect::default() {
+            return phones;
+        }
+
+        let mut phones = phones;
+
+        for i in 0..phones.len() {
+            let next = phones.get(i + 1).map(|p| p.base());
+            let base = match phones[i].base() {
+                "AA" if self.father_bother && lot(word, &phones[i + 1..]) => "OH",
+                "AE" if self.trap_bath && self.bath(&phones[i + 1..]) => "AA",
+                "AO" if self.cot_caught && next != Some("R") => "AA",
+                "EH" if self.pin_pen && matches!(next, Some("N" | "M" | "NG")) => "IH",
+                "AE" | "EY" if self.mary_marry_merry && next == Some("R") => "EH",
+                _ => continue,
+            };
+            phones.to_mut()[i] = vowel(base, phones[i]);
+        }
+
+        if self.non_rhotic && rhotic(&phones) {
+            phones = Cow::Owned(non_rhotic(&phones));
+        }
+
+        phones
+    }
+    /// Whether the phonemes after an "AE" make it the vowel of "bath".
+    fn bath(&self, rest: &[Phoneme]) -> bool {
+        let base = |i: usize| rest.get(i).map(|p| p.base());
+        let consonant = |i: usize| rest.get(i).is_none_or(|p| !p.is_vowel());
+
+        match base(0) {
+            Some("F" | "TH" | "S") => consonant(1),
+            Some("N") => self.trap_bath_nasals && matches!(base(1), Some("T" | "S" | "CH")),
+            _ => false,
+        }
+    }
+}
+
+/// Words with the vowel of "palm" rather than that of "lot" for an "AA" that
+/// isn't before "R".
+const PALM: &[&str] = &[
+    "ah", "aha", "almond", "alms", "bah", "balm", "blah", "bra", "bravo", "calm", "drama",
+    "facade", "father", "gala", "garage", "ha", "hurrah", "lasagna", "lava", "llama", "ma", "mama",
+    "massage", "mirage", "pa", "palm", "papa", "pasta", "plaza", "psalm", "qualm", "saga", "schwa",
+    "shah", "sonata", "spa", "tiara",
+];
+
+/// Whether the "AA" of `word` followed by `rest` is the vowel of "lot".
+fn lot(word: &str, rest: &[Phoneme]) -> bool {
+    let word = word.to_lowercase();
+
+    if rest.first().is_some_and(|p| p.base() == "R") {
+        // "sorry" and "quarrel" but "starry"
+        let vowel = rest.get(1).is_some_and(|p| p.is_vowel());
+        return vowel && (word.contains("or") || word.contains("war") || word.contains("quar"));
+    }
+
+    // short words only in their plurals, "spa" isn't in "spat"
+    !PALM.iter().any(|palm| {
+        if palm.len() > 3 {
+            word.contains(palm)
+        } else {
+            let rest = word.strip_prefix(palm).unwrap_or("-");
+            matches!(rest, "" | "s" | "'s")
+        }
+    })
+}
+
+/// Whether there's an "R" for `non_rhotic` to drop or an "ER" to reduce.
+fn rhotic(phones: &[Phoneme]) -> bool {
+    phones.iter().enumerate().any(|(i, phone)| {
+        let followed = phones.get(i + 1).is_some_and(|p| p.is_vowel());
+        phone.as_str() == "ER0" || (phone.base() == "R" && !followed)
+    })
+}
+
+/// Drop "R" where no vowel follows it, leaving a schwa after vowels that
+/// don't lengthen, and make unstressed "ER" a schwa.
+fn non_rhotic(phones: &[Phoneme]) -> Vec<Phoneme> {
+    let mut said = Vec::with_capacity(phones.len());
+
+    for (i, &phone) in phones.iter().enumerate() {
+        if phone.as_str() == "ER0" {
+            said.push(vowel("AH", phone));
+            continue;
+        }
+
+        let followed = phones.get(i + 1).is_some_and(|p| p.is_vowel());
+        if phone.base() != "R" || followed {
+            said.push(phone);
+            continue;
+        }
+
+        match said.last().copied() {
+            Some(p) if matches!(p.base(), "AA" | "AO" | "ER" | "OH") => {}
+            Some(p) if p.is_vowel() => said.extend("AH0".parse::<Phoneme>()),
+            _ => said.push(phone),
+        }
+    }
+
+    said
+}
+
+/// The vowel `base` with the stress of `like`.
+fn vowel(base: &str, like: Phoneme) -> Phoneme {
+    Phoneme::vowel(base, like.stress().unwrap_or(0)).unwrap_or(like)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_when_unchanged() {
+        let phones = |s: &str| {
+            s.split(' ')
+                .map(|p| p.parse().unwrap())
+                .collect::<Vec<Phoneme>>()
+        };
+        let british = Dialect::british();
+
+        let cat = phones("K AE1 T");
+        assert!(matches!(
+            british.pronounce("cat", Cow::Borrowed(&cat)),
+            Cow::Borrowed(_)
+        ));
+        let red = phones("R EH1 D");
+        assert!(matches!(
+            british.pronounce("red", Cow::Borrowed(&red)),
+            Cow::Borrowed(_)
+        ));
+        let car = phones("K AA1 R");
+        assert!(matches!(
+            british.pronounce("car", Cow::Borrowed(&car)),
+            Cow::Owned(_)
+        ));
+    }
+}
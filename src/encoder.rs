@@ -0,0 +1,282 @@
+use caverphone;
+use cologne;
+use daitch_mokotoff;
+use error::Error;
+use metaphone::{self, DoubleMetaphone};
+use nysiis;
+use original_metaphone;
+use refined_soundex;
+use soundex;
+use spanish;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A phonetic encoder that reduces a word to a key, words that sound alike
+/// share a key.
+pub trait Encoder {
+    /// Phonetic key of a word.
+    fn encode(&self, word: &str) -> String;
+
+    /// Every phonetic code of a word, for encoders that produce alternatives
+    /// for ambiguous spellings. Defaults to the single code from `encode`.
+    fn encode_all(&self, word: &str) -> PhoneticCodes {
+        PhoneticCodes::from(self.encode(word))
+    }
+
+    /// Whether two words share a, non-empty, phonetic code.
+    fn is_match(&self, a: &str, b: &str) -> bool {
+        self.encode_all(a).intersects(&self.encode_all(b))
+    }
+}
+
+impl<T: Encoder + ?Sized> Encoder for &T {
+    fn encode(&self, word: &str) -> String {
+        (**self).encode(word)
+    }
+
+    fn encode_all(&self, word: &str) -> PhoneticCodes {
+        (**self).encode_all(word)
+    }
+
+    fn is_match(&self, a: &str, b: &str) -> bool {
+        (**self).is_match(a, b)
+    }
+}
+
+/// The phonetic codes of a word, in order of preference. Most encoders
+/// produce a single code, Double Metaphone produces a primary and secondary
+/// code, and Daitch-Mokotoff any number of alternatives.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::encoder::PhoneticCodes;
+/// use ttaw::metaphone;
+///
+/// let codes = PhoneticCodes::from(metaphone::encoding("Arnow"));
+/// assert_eq!(codes.primary(), Some("ARN"));
+/// assert_eq!(codes.len(), 2);
+/// assert_eq!(codes.to_string(), "ARN|ARNF");
+///
+/// // duplicates and empty codes are dropped
+/// assert_eq!(PhoneticCodes::new(vec!["TTSTPL", "", "TTSTPL"]).len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PhoneticCodes(Vec<String>);
+
+impl PhoneticCodes {
+    pub fn new<I, S>(codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        // the set finds duplicates, the Vec keeps the order
+        let mut seen = HashSet::new();
+        let mut unique: Vec<String> = vec![];
+
+        for code in codes {
+            let code = code.into();
+            if !code.is_empty() && seen.insert(code.clone()) {
+                unique.push(code);
+            }
+        }
+
+        PhoneticCodes(unique)
+    }
+
+    /// The preferred code, `None` if the word couldn't be encoded.
+    pub fn primary(&self) -> Option<&str> {
+        self.0.first().map(|c| c.as_str())
+    }
+
+    /// Whether any code is shared with `other`.
+    pub fn intersects(&self, other: &PhoneticCodes) -> bool {
+        self.0.iter().any(|c| other.0.contains(c))
+    }
+
+    pub fn into_vec(self) -> Vec<String> {
+        self.0
+    }
+}
+
+impl Deref for PhoneticCodes {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl IntoIterator for PhoneticCodes {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PhoneticCodes {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl From<String> for PhoneticCodes {
+    fn from(code: String) -> Self {
+        PhoneticCodes::new(vec![code])
+    }
+}
+
+impl From<DoubleMetaphone> for PhoneticCodes {
+    fn from(encoding: DoubleMetaphone) -> Self {
+        PhoneticCodes::new(vec![encoding.primary, encoding.secondary])
+    }
+}
+
+impl fmt::Display for PhoneticCodes {
+    /// Codes joined with '|'.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join("|"))
+    }
+}
+
+/// Whether two words rhyme given their phonetic codes, i.e. whether any code of
+/// one matches any code of the other after the first sound.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::encoder::codes_rhyme;
+/// assert!(codes_rhyme(&["FR"], &["TR", "TRS"]));
+/// assert!(!codes_rhyme(&["FR"], &["TRS"]));
+/// ```
+pub fn codes_rhyme<S: AsRef<str>>(a: &[S], b: &[S]) -> bool {
+    let tail = |code: &str| code.chars().skip(1).collect::<String>();
+    any_pair(a, b, |a, b| tail(a) == tail(b))
+}
+
+/// Whether two words alliterate given their phonetic codes, i.e. whether any
+/// code of one starts with the same sound as any code of the other.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::encoder::codes_alliterate;
+/// assert!(codes_alliterate(&["PNSNK"], &["PRS"]));
+/// assert!(!codes_alliterate(&["LS"], &["TK"]));
+/// ```
+pub fn codes_alliterate<S: AsRef<str>>(a: &[S], b: &[S]) -> bool {
+    any_pair(a, b, |a, b| a.chars().next() == b.chars().next())
+}
+
+fn any_pair<S: AsRef<str>, F: Fn(&str, &str) -> bool>(a: &[S], b: &[S], f: F) -> bool {
+    a.iter()
+        .any(|a| b.iter().any(|b| f(a.as_ref(), b.as_ref())))
+}
+
+/// The phonetic algorithms available in ttaw. Algorithms can be parsed from
+/// their names, so the one in use can come from configuration.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::encoder::{Algorithm, Encoder};
+///
+/// let algorithm: Algorithm = "soundex".parse().unwrap();
+/// assert_eq!(algorithm.encode("Robert"), "R163");
+/// assert!(algorithm.is_match("Robert", "Rupert"));
+///
+/// let algorithm: Algorithm = "double-metaphone".parse().unwrap();
+/// assert_eq!(algorithm.encode("Arnow"), "ARN");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Soundex,
+    RefinedSoundex,
+    Nysiis,
+    Metaphone,
+    DoubleMetaphone,
+    Caverphone,
+    Cologne,
+    Spanish,
+    DaitchMokotoff,
+}
+
+impl Algorithm {
+    /// Every available algorithm.
+    pub const ALL: &'static [Algorithm] = &[
+        Algorithm::Soundex,
+        Algorithm::RefinedSoundex,
+        Algorithm::Nysiis,
+        Algorithm::Metaphone,
+        Algorithm::DoubleMetaphone,
+        Algorithm::Caverphone,
+        Algorithm::Cologne,
+        Algorithm::Spanish,
+        Algorithm::DaitchMokotoff,
+    ];
+
+    /// Name of the algorithm, as accepted by `from_str`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Soundex => "soundex",
+            Algorithm::RefinedSoundex => "refined_soundex",
+            Algorithm::Nysiis => "nysiis",
+            Algorithm::Metaphone => "metaphone",
+            Algorithm::DoubleMetaphone => "double_metaphone",
+            Algorithm::Caverphone => "caverphone",
+            Algorithm::Cologne => "cologne",
+            Algorithm::Spanish => "spanish",
+            Algorithm::DaitchMokotoff => "daitch_mokotoff",
+        }
+    }
+}
+
+impl Encoder for Algorithm {
+    /// Phonetic key of a word. For Double Metaphone this is the primary encoding,
+    /// for algorithms with more alternatives every code joined with '|'.
+    fn encode(&self, word: &str) -> String {
+        match self {
+            Algorithm::Soundex => soundex::encoding(word),
+            Algorithm::RefinedSoundex => refined_soundex::encoding(word),
+            Algorithm::Nysiis => nysiis::encoding(word),
+            Algorithm::Metaphone => original_metaphone::encoding(word),
+            Algorithm::DoubleMetaphone => metaphone::encoding(word).primary,
+            Algorithm::Caverphone => caverphone::encoding(word),
+            Algorithm::Cologne => cologne::encoding(word),
+            Algorithm::Spanish => spanish::encoding(word),
+            Algorithm::DaitchMokotoff => self.encode_all(word).to_string(),
+        }
+    }
+
+    fn encode_all(&self, word: &str) -> PhoneticCodes {
+        match self {
+            Algorithm::DoubleMetaphone => metaphone::encoding(word).into(),
+            Algorithm::DaitchMokotoff => daitch_mokotoff::encoding(word),
+            _ => self.encode(word).into(),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    /// Parse an algorithm name, ignoring case and treating '-' and ' ' as '_'.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['-', ' '], "_");
+
+        Algorithm::ALL
+            .iter()
+            .find(|a| a.name() == name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownAlgorithm(s.to_string()))
+    }
+}
@@ -1,45 +1,209 @@
-
 use std::fmt;
+use std::io;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, PartialEq)]
+/// Errors from ttaw. Underlying io, JSON and download errors are kept as the
+/// `source` of the error, in an `Arc` so errors stay cheap to clone.
+#[derive(Debug, Clone)]
 pub enum Error {
+    #[deprecated(note = "errors have their own variants now")]
     InputError(String),
+    #[deprecated(note = "errors have their own variants now")]
     ProgramError(String),
+    /// Reading or writing a file failed.
+    Io(Arc<io::Error>),
+    /// Parsing JSON failed.
+    Json(Arc<serde_json::Error>),
+    /// Downloading the dictionary failed.
+    Download {
+        url: String,
+        source: Arc<reqwest::Error>,
+    },
+    /// A downloaded dictionary didn't have the expected SHA-256 digest.
+    ChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+    /// A dictionary that can't be loaded. `origin` is the file, URL or
+    /// "embedded data" it came from and `line` is set when the bad line is known.
+    CorruptDictionary {
+        origin: String,
+        line: Option<usize>,
+        source: Box<Error>,
+    },
+    /// The word isn't in the dictionary.
+    UnknownWord(String),
+    /// Not an ARPAbet phoneme.
+    InvalidPhoneme(String),
+    /// A dictionary entry without a pronunciation, or with an empty one.
+    EmptyPronunciation(String),
+    /// No dictionary was found where `CmuDictBuilder` looked and downloading
+    /// is off.
+    DictionaryUnavailable,
+    /// A phonetic index file that can't be loaded, with the bad line and
+    /// what's wrong with it.
+    CorruptIndex {
+        path: String,
+        line: usize,
+        reason: String,
+    },
+    /// Not the name of a phonetic algorithm.
+    UnknownAlgorithm(String),
 }
 
+#[allow(deprecated)]
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             // Error::ParseError(ref s) => write!(f, "{}", s),
             Error::InputError(ref s) => write!(f, "{}", s),
             Error::ProgramError(ref s) => write!(f, "{}", s),
+            Error::Io(ref e) => write!(f, "{}", e),
+            Error::Json(ref e) => write!(f, "{}", e),
+            Error::Download {
+                ref url,
+                ref source,
+            } => write!(f, "failed to download {}: {}", url, source),
+            Error::ChecksumMismatch {
+                ref url,
+                ref expected,
+                ref actual,
+            } => write!(
+                f,
+                "checksum mismatch for {}: expected {}, got {}",
+                url, expected, actual
+            ),
+            Error::CorruptDictionary {
+                ref origin,
+                line,
+                ref source,
+            } => {
+                write!(f, "corrupt CMU dictionary from {}", origin)?;
+                if let Some(line) = line {
+                    write!(f, ", line {}", line)?;
+                }
+                write!(f, ": {}", source)
+            }
+            Error::UnknownWord(ref w) => write!(f, "word not in the dictionary: {}", w),
+            Error::InvalidPhoneme(ref p) => write!(f, "invalid phoneme: {}", p),
+            Error::EmptyPronunciation(ref w) => write!(f, "no pronunciation for {}", w),
+            Error::DictionaryUnavailable => {
+                write!(f, "no CMU dictionary found and downloading is off")
+            }
+            Error::CorruptIndex {
+                ref path,
+                line,
+                ref reason,
+            } => write!(
+                f,
+                "corrupt phonetic index {}, line {}: {}",
+                path, line, reason
+            ),
+            Error::UnknownAlgorithm(ref a) => write!(f, "unknown phonetic algorithm: {}", a),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e.as_ref()),
+            Error::Json(ref e) => Some(e.as_ref()),
+            Error::Download { ref source, .. } => Some(source.as_ref()),
+            Error::CorruptDictionary { ref source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[allow(deprecated)]
+impl PartialEq for Error {
+    /// Errors are equal when they're the same kind with the same details,
+    /// underlying errors are compared by their messages.
+    fn eq(&self, other: &Error) -> bool {
+        match (self, other) {
+            (Error::InputError(a), Error::InputError(b)) => a == b,
+            (Error::ProgramError(a), Error::ProgramError(b)) => a == b,
+            (Error::Io(a), Error::Io(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+            (Error::Json(a), Error::Json(b)) => a.to_string() == b.to_string(),
+            (
+                Error::Download { url, source },
+                Error::Download {
+                    url: other_url,
+                    source: other_source,
+                },
+            ) => url == other_url && source.to_string() == other_source.to_string(),
+            (
+                Error::ChecksumMismatch {
+                    url,
+                    expected,
+                    actual,
+                },
+                Error::ChecksumMismatch {
+                    url: other_url,
+                    expected: other_expected,
+                    actual: other_actual,
+                },
+            ) => url == other_url && expected == other_expected && actual == other_actual,
+            (
+                Error::CorruptDictionary {
+                    origin,
+                    line,
+                    source,
+                },
+                Error::CorruptDictionary {
+                    origin: other_origin,
+                    line: other_line,
+                    source: other_source,
+                },
+            ) => origin == other_origin && line == other_line && source == other_source,
+            (Error::UnknownWord(a), Error::UnknownWord(b)) => a == b,
+            (Error::InvalidPhoneme(a), Error::InvalidPhoneme(b)) => a == b,
+            (Error::EmptyPronunciation(a), Error::EmptyPronunciation(b)) => a == b,
+            (Error::DictionaryUnavailable, Error::DictionaryUnavailable) => true,
+            (
+                Error::CorruptIndex { path, line, reason },
+                Error::CorruptIndex {
+                    path: other_path,
+                    line: other_line,
+                    reason: other_reason,
+                },
+            ) => path == other_path && line == other_line && reason == other_reason,
+            (Error::UnknownAlgorithm(a), Error::UnknownAlgorithm(b)) => a == b,
+            _ => false,
         }
     }
 }
 
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
-        Error::InputError(format!("{}", err))
+        Error::Io(Arc::new(err))
     }
 }
 
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
-        Error::InputError(format!("{}", err))
+        Error::Json(Arc::new(err))
     }
 }
 
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
-        Error::InputError(format!("{}", err))
+        Error::Download {
+            url: err.url().map(|u| u.to_string()).unwrap_or_default(),
+            source: Arc::new(err),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::error::Error as StdError;
 
     #[test]
+    #[allow(deprecated)]
     fn display() {
         let input = "input error".to_string();
         let progam = "program error".to_string();
@@ -51,8 +215,32 @@ mod tests {
     #[test]
     fn io_err() {
         let err_str = "IO Errored!";
-        let error = std::io::Error::new(std::io::ErrorKind::Other, err_str);
+        let error = std::io::Error::other(err_str);
 
         assert_eq!(err_str.to_string(), format!("{}", Error::from(error)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn sources() {
+        let error = Error::from(std::io::Error::other("IO Errored!"));
+        assert_eq!(error.source().unwrap().to_string(), "IO Errored!");
+        assert_eq!(error.clone(), error);
+
+        let json = serde_json::from_str::<Vec<String>>("[1").unwrap_err();
+        let error = Error::CorruptDictionary {
+            origin: "cmudict.json".to_string(),
+            line: Some(1),
+            source: Box::new(json.into()),
+        };
+        assert!(error
+            .to_string()
+            .starts_with("corrupt CMU dictionary from cmudict.json, line 1: "));
+        assert!(matches!(error.source(), Some(e) if e.source().is_some()));
+
+        assert!(Error::UnknownWord("zzz".to_string()).source().is_none());
+        assert_ne!(
+            Error::UnknownWord("zzz".to_string()),
+            Error::InvalidPhoneme("zzz".to_string())
+        );
+    }
+}
@@ -0,0 +1,413 @@
+use cmu::CmuDict;
+use error::Error;
+use phonetic::{Chain, Metaphone, RhymeChecker, Verdict};
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+use text::{self, is_function_word};
+
+/// A fixed poetic form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Form {
+    /// Three lines of 5, 7 and 5 syllables.
+    Haiku,
+    /// Five lines of 5, 7, 5, 7 and 7 syllables.
+    Tanka,
+    /// Five lines rhyming AABBA in anapestic meter, three beats to the long
+    /// lines and two to the short ones.
+    Limerick,
+    /// Fourteen lines of iambic pentameter, ten or eleven syllables, rhyming
+    /// ABAB CDCD EFEF GG.
+    ShakespeareanSonnet,
+    /// Fourteen lines of iambic pentameter, ten or eleven syllables, rhyming
+    /// ABBAABBA CDECDE.
+    PetrarchanSonnet,
+}
+
+/// The meter of a form's lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Meter {
+    /// Two unstressed syllables before each stressed one, "da-da-DUM", with
+    /// fewer before the first.
+    Anapestic,
+    /// Unstressed and stressed syllables alternating, "da-DUM".
+    Iambic,
+}
+
+impl fmt::Display for Meter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Meter::Anapestic => write!(f, "anapestic"),
+            Meter::Iambic => write!(f, "iambic"),
+        }
+    }
+}
+
+/// What a line must be like in a form.
+struct LineSpec {
+    syllables: RangeInclusive<usize>,
+    rhyme: Option<char>,
+    /// The meter and its number of beats.
+    meter: Option<(Meter, usize)>,
+}
+
+impl Form {
+    fn lines(self) -> Vec<LineSpec> {
+        let syllables = |counts: &[usize]| {
+            counts
+                .iter()
+                .map(|&n| LineSpec {
+                    syllables: n..=n,
+                    rhyme: None,
+                    meter: None,
+                })
+                .collect()
+        };
+        let sonnet = |scheme: &str| {
+            scheme
+                .chars()
+                .map(|letter| LineSpec {
+                    syllables: 10..=11,
+                    rhyme: Some(letter),
+                    meter: Some((Meter::Iambic, 5)),
+                })
+                .collect()
+        };
+
+        match self {
+            Form::Haiku => syllables(&[5, 7, 5]),
+            Form::Tanka => syllables(&[5, 7, 5, 7, 7]),
+            Form::Limerick => "AABBA"
+                .chars()
+                .map(|letter| {
+                    let long = letter == 'A';
+                    LineSpec {
+                        syllables: if long { 7..=10 } else { 4..=7 },
+                        rhyme: Some(letter),
+                        meter: Some((Meter::Anapestic, if long { 3 } else { 2 })),
+                    }
+                })
+                .collect(),
+            Form::ShakespeareanSonnet => sonnet("ABABCDCDEFEFGG"),
+            Form::PetrarchanSonnet => sonnet("ABBAABBACDECDE"),
+        }
+    }
+}
+
+/// Why a poem doesn't fit its form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The poem has the wrong number of lines.
+    Lines { expected: usize, actual: usize },
+    /// The line has the wrong number of syllables.
+    Syllables {
+        expected: RangeInclusive<usize>,
+        actual: usize,
+    },
+    /// The line doesn't rhyme with the earlier `line` it shares `letter` of
+    /// the rhyme scheme with, or the dictionary doesn't know one of their last
+    /// words and the `verdict` is only guessed from their spelling.
+    Rhyme {
+        letter: char,
+        line: usize,
+        verdict: Verdict,
+    },
+    /// The line doesn't scan as `meter` with `beats` beats.
+    Meter { meter: Meter, beats: usize },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Problem::Lines { expected, actual } => {
+                write!(f, "expected {} lines, got {}", expected, actual)
+            }
+            Problem::Syllables {
+                ref expected,
+                actual,
+            } if expected.start() == expected.end() => {
+                write!(f, "expected {} syllables, got {}", expected.start(), actual)
+            }
+            Problem::Syllables {
+                ref expected,
+                actual,
+            } => write!(
+                f,
+                "expected {} to {} syllables, got {}",
+                expected.start(),
+                expected.end(),
+                actual
+            ),
+            Problem::Rhyme {
+                letter,
+                line,
+                ref verdict,
+            } if !verdict.missing().is_empty() => {
+                write!(
+                    f,
+                    "can't tell if it rhymes with line {} ({}), unknown words: {}",
+                    line + 1,
+                    letter,
+                    verdict.missing().join(", ")
+                )?;
+                match *verdict {
+                    Verdict::Fallback { ref verdict, .. } if verdict.is_yes() => {
+                        write!(f, ", it does by their spelling")
+                    }
+                    Verdict::Fallback { .. } => write!(f, ", it doesn't by their spelling"),
+                    _ => Ok(()),
+                }
+            }
+            Problem::Rhyme { letter, line, .. } => {
+                write!(f, "doesn't rhyme with line {} ({})", line + 1, letter)
+            }
+            Problem::Meter { meter, beats } => {
+                write!(f, "doesn't scan as {} meter with {} beats", meter, beats)
+            }
+        }
+    }
+}
+
+/// A line of a poem checked against its form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Byte range of the line in the poem.
+    pub span: Range<usize>,
+    pub syllables: usize,
+    /// Stress of each syllable: "/" stressed, "u" unstressed and "?" either,
+    /// for one syllable words and secondary stress.
+    pub stress: String,
+    /// Words not in the dictionary, their syllables guessed from the spelling.
+    pub unknown: Vec<String>,
+    /// The line's letter in the rhyme scheme.
+    pub rhyme: Option<char>,
+    pub problems: Vec<Problem>,
+}
+
+/// A poem checked against a form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub form: Form,
+    /// The lines of the poem, blank lines left out.
+    pub lines: Vec<Line>,
+    /// Problems with the poem as a whole.
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.problems.is_empty() && self.lines.iter().all(|line| line.problems.is_empty())
+    }
+}
+
+/// Checks poems against fixed forms, counting syllables and finding stress
+/// with the CMU dictionary and telling rhymes with the dictionary. Rhymes of
+/// words the dictionary doesn't know are guessed with Double Metaphone and
+/// reported as problems either way, naming the unknown words.
+///
+/// ```rust,no_run
+/// extern crate ttaw;
+/// use ttaw::cmu::CmuDict;
+/// use ttaw::forms::{Form, Validator};
+///
+/// let validator = Validator::new(CmuDict::new("cmudict.json").unwrap());
+/// let report = validator
+///     .validate(
+///         Form::Haiku,
+///         "An old silent pond\nA frog jumps into the pond\nSplash! Silence again",
+///     )
+///     .unwrap();
+///
+/// assert!(report.passed());
+/// assert_eq!(report.lines[1].syllables, 7);
+/// ```
+#[derive(Clone)]
+pub struct Validator {
+    cmudict: CmuDict,
+}
+
+impl Validator {
+    pub fn new(cmudict: CmuDict) -> Self {
+        Validator { cmudict }
+    }
+
+    pub fn validate(&self, form: Form, poem: &str) -> Result<Report, Error> {
+        let specs = form.lines();
+        let mut lines = vec![];
+        // the last word of each line
+        let mut ends: Vec<Option<String>> = vec![];
+        // whether the stress of each syllable of each line is that of a word
+        // of more than one syllable, which meter can't move
+        let mut lexical: Vec<Vec<bool>> = vec![];
+        let mut start = 0;
+
+        for text in poem.split('\n') {
+            let span = start..start + text.len();
+            start = span.end + 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            let mut line = Line {
+                span,
+                syllables: 0,
+                stress: String::new(),
+                unknown: vec![],
+                rhyme: None,
+                problems: vec![],
+            };
+
+            let tokens = text::tokenize(text);
+            let mut fixed = vec![];
+            for token in &tokens {
+                let stress = self.stress(&token.word, &mut line.unknown);
+                let syllables = stress.chars().count();
+                fixed.extend(std::iter::repeat_n(syllables > 1, syllables));
+                line.stress.push_str(&stress);
+            }
+            line.syllables = line.stress.chars().count();
+            ends.push(tokens.last().map(|token| token.word.clone()));
+            lexical.push(fixed);
+            lines.push(line);
+        }
+
+        let checker = Chain::new(&self.cmudict, Metaphone);
+        for (i, spec) in specs.iter().enumerate().take(lines.len()) {
+            let mut problems = vec![];
+
+            if !spec.syllables.contains(&lines[i].syllables) {
+                problems.push(Problem::Syllables {
+                    expected: spec.syllables.clone(),
+                    actual: lines[i].syllables,
+                });
+            }
+
+            if let Some((meter, beats)) = spec.meter {
+                let scans = match meter {
+                    Meter::Anapestic => anapestic(&lines[i].stress, beats),
+                    Meter::Iambic => iambic(&lines[i].stress, &lexical[i], beats),
+                };
+                if !scans {
+                    problems.push(Problem::Meter { meter, beats });
+                }
+            }
+
+            if let Some(letter) = spec.rhyme {
+                let earlier = specs[..i].iter().position(|s| s.rhyme == Some(letter));
+                if let Some(j) = earlier {
+                    let verdict = match (&ends[j], &ends[i]) {
+                        (Some(a), Some(b)) => checker.rhyme_verdict(a, b)?,
+                        _ => Verdict::No,
+                    };
+                    // a guess from the spelling isn't a pass or a failure
+                    if !verdict.is_yes() || !verdict.missing().is_empty() {
+                        problems.push(Problem::Rhyme {
+                            letter,
+                            line: j,
+                            verdict,
+                        });
+                    }
+                }
+                lines[i].rhyme = Some(letter);
+            }
+
+            lines[i].problems = problems;
+        }
+
+        let mut problems = vec![];
+        if lines.len() != specs.len() {
+            problems.push(Problem::Lines {
+                expected: specs.len(),
+                actual: lines.len(),
+            });
+        }
+
+        Ok(Report {
+            form,
+            lines,
+            problems,
+        })
+    }
+
+    /// The stress of each syllable of a word, see `Line::stress`.
+    fn stress(&self, word: &str, unknown: &mut Vec<String>) -> String {
+        let pronunciation = self
+            .cmudict
+            .pronunciations(word)
+            .and_then(|mut pronunciations| pronunciations.next());
+
+        let phones = match pronunciation {
+            Some(phones) => phones,
+            None => {
+                unknown.push(word.to_string());
+                return "?".repeat(text::estimate_syllables(word));
+            }
+        };
+
+        let vowels = phones.iter().filter(|p| p.is_vowel()).collect::<Vec<_>>();
+        if vowels.len() == 1 {
+            return if is_function_word(word) { "u" } else { "?" }.to_string();
+        }
+
+        vowels
+            .iter()
+            .map(|p| match p.stress() {
+                Some(1) => '/',
+                Some(0) => 'u',
+                _ => '?',
+            })
+            .collect()
+    }
+}
+
+/// Whether a line with the given stresses can be read as anapestic meter with
+/// `beats` beats: up to two unstressed syllables before the first beat, two
+/// or three between beats and up to two after the last.
+fn anapestic(stress: &str, beats: usize) -> bool {
+    // the beats so far and the unstressed syllables since the last one, or
+    // since the start of the line
+    let mut states: HashSet<(usize, usize)> = HashSet::new();
+    states.insert((0, 0));
+
+    for syllable in stress.chars() {
+        let mut next = HashSet::new();
+        for &(count, unstressed) in &states {
+            if syllable != '/' && unstressed < if count == 0 { 2 } else { 3 } {
+                next.insert((count, unstressed + 1));
+            }
+            if syllable != 'u' && count < beats && (count == 0 || unstressed >= 2) {
+                next.insert((count + 1, 0));
+            }
+        }
+        states = next;
+    }
+
+    states
+        .iter()
+        .any(|&(count, unstressed)| count == beats && unstressed <= 2)
+}
+
+/// Whether a line with the given stresses can be read as iambic meter with
+/// `beats` beats, every second syllable stressed. The stress of words of more
+/// than one syllable, `lexical`, must fall on those beats, except in an
+/// inverted first foot, while one syllable words fit either way, so feet of
+/// two unstressed or two stressed syllables are allowed where they are made of
+/// them. An unstressed syllable may follow the last beat.
+fn iambic(stress: &str, lexical: &[bool], beats: usize) -> bool {
+    let syllables = stress.chars().collect::<Vec<char>>();
+    if syllables.len() != beats * 2 && syllables.len() != beats * 2 + 1 {
+        return false;
+    }
+
+    let inverted = syllables[0] == '/';
+    syllables
+        .iter()
+        .zip(lexical)
+        .enumerate()
+        .skip(if inverted { 2 } else { 0 })
+        .all(|(i, (&syllable, &lexical))| {
+            let beat = i % 2 == 1 && i < beats * 2;
+            !lexical || syllable == '?' || (syllable == '/') == beat
+        })
+        && syllables.get(beats * 2) != Some(&'/')
+}
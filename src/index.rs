@@ -0,0 +1,388 @@
+use atomic;
+use encoder::PhoneticCodes;
+use error::Error;
+use metaphone;
+use similarity;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A search index of names by their Double Metaphone codes. Names are found by
+/// either their primary or secondary code, so "Smith" finds "Schmidt", and
+/// candidates are ranked by how similar they are to the query in spelling.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::index::PhoneticIndex;
+///
+/// let mut index = PhoneticIndex::new();
+/// index.insert(1, "Catherine");
+/// index.insert(2, "Kathryn");
+/// index.insert(3, "Robert");
+///
+/// let matches = index.query("Katherine");
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches[0].id, 1);
+/// assert_eq!(matches[1].id, 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhoneticIndex<Id> {
+    /// Phonetic code to the ids of the records with that code.
+    codes: HashMap<String, HashSet<Id>>,
+    records: HashMap<Id, Record>,
+}
+
+#[derive(Debug, Clone)]
+struct Record {
+    name: String,
+    codes: PhoneticCodes,
+}
+
+/// A candidate found by `PhoneticIndex::query`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<Id> {
+    pub id: Id,
+    pub name: String,
+    /// Between 0.0 and 1.0, higher is a better match.
+    pub score: f64,
+}
+
+impl<Id: Eq + Hash + Clone> PhoneticIndex<Id> {
+    pub fn new() -> Self {
+        PhoneticIndex {
+            codes: HashMap::new(),
+            records: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Index a name under `id`, replacing and returning the name previously
+    /// indexed under it.
+    pub fn insert(&mut self, id: Id, name: &str) -> Option<String> {
+        let codes = PhoneticCodes::from(metaphone::encoding(name));
+        self.insert_encoded(id, name, codes)
+    }
+
+    fn insert_encoded(&mut self, id: Id, name: &str, codes: PhoneticCodes) -> Option<String> {
+        let previous = self.remove(&id);
+
+        for code in &codes {
+            self.codes
+                .entry(code.to_string())
+                .or_default()
+                .insert(id.clone());
+        }
+
+        self.records.insert(
+            id,
+            Record {
+                name: name.to_string(),
+                codes,
+            },
+        );
+
+        previous
+    }
+
+    /// Remove the record `id`, returning its name.
+    pub fn remove(&mut self, id: &Id) -> Option<String> {
+        let record = self.records.remove(id)?;
+
+        for code in &record.codes {
+            if let Some(ids) = self.codes.get_mut(code) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.codes.remove(code);
+                }
+            }
+        }
+
+        Some(record.name)
+    }
+
+    /// The name indexed under `id`.
+    pub fn get(&self, id: &Id) -> Option<&str> {
+        self.records.get(id).map(|r| r.name.as_str())
+    }
+
+    /// Records sharing a phonetic code with `name`, best match first.
+    ///
+    /// The score weighs the phonetic match, full for records sharing the
+    /// query's primary code and half for a match on a secondary code, equally
+    /// with the Jaro-Winkler similarity of the spellings.
+    pub fn query(&self, name: &str) -> Vec<Match<Id>> {
+        let codes = PhoneticCodes::from(metaphone::encoding(name));
+
+        let mut matches: Vec<Match<Id>> = vec![];
+        let mut seen = HashSet::new();
+
+        for code in &codes {
+            for id in self.codes.get(code).into_iter().flatten() {
+                if !seen.insert(id) {
+                    continue;
+                }
+
+                let record = &self.records[id];
+
+                matches.push(Match {
+                    id: id.clone(),
+                    name: record.name.clone(),
+                    score: score(name, &codes, &record.name, &record.codes),
+                });
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        matches
+    }
+}
+
+/// How alike two names sharing a phonetic code are, see `PhoneticIndex::query`.
+pub(crate) fn score(a: &str, a_codes: &PhoneticCodes, b: &str, b_codes: &PhoneticCodes) -> f64 {
+    let phonetic = if a_codes.primary() == b_codes.primary() {
+        1.0
+    } else {
+        0.5
+    };
+    let spelling = similarity::jaro_winkler(&a.to_lowercase(), &b.to_lowercase());
+
+    (phonetic + spelling) / 2.0
+}
+
+/// The version of the `PersistentIndex` file format. Bumped whenever the
+/// format or the phonetic encoding changes, since codes are stored rather than
+/// recomputed.
+pub const FORMAT_VERSION: u32 = 1;
+
+const HEADER: &str = "ttaw-phonetic-index";
+
+/// A `PhoneticIndex` kept on disk as a log of inserts and removals. Each
+/// change appends a line to the file, so updates never rewrite it, and
+/// `compact` rewrites the log with only the live records. Codes are stored
+/// with the names so loading doesn't encode every name again.
+///
+/// ```rust
+/// extern crate tempfile;
+/// extern crate ttaw;
+/// use ttaw::index::PersistentIndex;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("names.idx");
+///
+/// let mut index = PersistentIndex::open(&path).unwrap();
+/// index.insert(1, "Smith").unwrap();
+/// index.insert(2, "Schmidt").unwrap();
+/// drop(index);
+///
+/// let index = PersistentIndex::<u32>::open(&path).unwrap();
+/// assert_eq!(index.query("Smyth")[0].id, 1);
+/// ```
+#[derive(Debug)]
+pub struct PersistentIndex<Id> {
+    index: PhoneticIndex<Id>,
+    path: PathBuf,
+    file: File,
+    /// Length of the log up to its last complete line.
+    len: u64,
+    /// Lines in the log that no longer describe a live record.
+    stale: usize,
+}
+
+impl<Id> PersistentIndex<Id>
+where
+    Id: Eq + Hash + Clone + ToString + FromStr,
+{
+    /// Load the index at `path`, creating an empty one if there's no file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+
+        if !path.exists() {
+            fs::write(&path, format!("{} {}\n", HEADER, FORMAT_VERSION))?;
+        }
+
+        let mut reader = BufReader::new(File::open(&path)?);
+        let mut index = PhoneticIndex::new();
+        let mut stale = 0;
+
+        let corrupt = |n: usize, reason: String| Error::CorruptIndex {
+            path: path.display().to_string(),
+            line: n + 1,
+            reason,
+        };
+
+        // bytes up to the end of the last complete line, a line without its
+        // newline is an interrupted append and is dropped
+        let mut complete = 0;
+        let mut line = String::new();
+
+        for n in 0.. {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 || !line.ends_with('\n') {
+                if n == 0 {
+                    return Err(corrupt(0, "not a phonetic index".to_string()));
+                }
+                break;
+            }
+            complete += read;
+
+            let line = line.trim_end_matches('\n').trim_end_matches('\r');
+
+            if n == 0 {
+                if line == format!("{} {}", HEADER, FORMAT_VERSION) {
+                    continue;
+                }
+                if line.starts_with(HEADER) {
+                    return Err(corrupt(
+                        0,
+                        format!(
+                            "unsupported version {}",
+                            line.trim_start_matches(HEADER).trim()
+                        ),
+                    ));
+                }
+                return Err(corrupt(0, "not a phonetic index".to_string()));
+            }
+
+            let entry =
+                serde_json::from_str::<Vec<String>>(line).map_err(|e| corrupt(n, e.to_string()))?;
+            let parse_id = |id: &str| {
+                id.parse::<Id>()
+                    .map_err(|_| corrupt(n, format!("invalid record id {:?}", id)))
+            };
+
+            match entry.split_first() {
+                Some((op, rest)) if op == "+" && rest.len() >= 2 => {
+                    let codes = PhoneticCodes::new(rest[2..].iter().cloned());
+                    if index
+                        .insert_encoded(parse_id(&rest[0])?, &rest[1], codes)
+                        .is_some()
+                    {
+                        stale += 1;
+                    }
+                }
+                Some((op, rest)) if op == "-" && rest.len() == 1 => {
+                    // the removal and the insert it undoes
+                    if index.remove(&parse_id(&rest[0])?).is_some() {
+                        stale += 2;
+                    } else {
+                        stale += 1;
+                    }
+                }
+                _ => return Err(corrupt(n, "invalid entry".to_string())),
+            }
+        }
+
+        let file = OpenOptions::new().append(true).open(&path)?;
+        file.set_len(complete as u64)?;
+
+        Ok(PersistentIndex {
+            index,
+            path,
+            file,
+            len: complete as u64,
+            stale,
+        })
+    }
+
+    /// Index a name under `id`, replacing and returning the name previously
+    /// indexed under it.
+    pub fn insert(&mut self, id: Id, name: &str) -> Result<Option<String>, Error> {
+        let codes = PhoneticCodes::from(metaphone::encoding(name));
+
+        let mut entry = vec!["+".to_string(), id.to_string(), name.to_string()];
+        entry.extend(codes.iter().cloned());
+        self.append(&entry)?;
+
+        let previous = self.index.insert_encoded(id, name, codes);
+        if previous.is_some() {
+            self.stale += 1;
+        }
+
+        Ok(previous)
+    }
+
+    /// Remove the record `id`, returning its name.
+    pub fn remove(&mut self, id: &Id) -> Result<Option<String>, Error> {
+        if self.index.get(id).is_none() {
+            return Ok(None);
+        }
+
+        self.append(&["-".to_string(), id.to_string()])?;
+        self.stale += 2;
+
+        Ok(self.index.remove(id))
+    }
+
+    /// Rewrite the file with only the live records.
+    pub fn compact(&mut self) -> Result<(), Error> {
+        let records = &self.index.records;
+        atomic::write(&self.path, |file| {
+            let mut writer = BufWriter::new(file);
+            writeln!(writer, "{} {}", HEADER, FORMAT_VERSION)?;
+
+            for (id, record) in records {
+                let mut entry = vec!["+".to_string(), id.to_string(), record.name.clone()];
+                entry.extend(record.codes.iter().cloned());
+                writeln!(writer, "{}", serde_json::to_string(&entry)?)?;
+            }
+
+            Ok(writer.flush()?)
+        })?;
+
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        self.len = self.file.metadata()?.len();
+        self.stale = 0;
+
+        Ok(())
+    }
+
+    /// Number of lines in the file that compaction would drop.
+    pub fn stale(&self) -> usize {
+        self.stale
+    }
+
+    /// Flush appended changes to the disk.
+    pub fn sync(&self) -> Result<(), Error> {
+        Ok(self.file.sync_data()?)
+    }
+
+    /// The in-memory index, for lookups.
+    pub fn index(&self) -> &PhoneticIndex<Id> {
+        &self.index
+    }
+
+    /// Records sharing a phonetic code with `name`, best match first. See
+    /// `PhoneticIndex::query`.
+    pub fn query(&self, name: &str) -> Vec<Match<Id>> {
+        self.index.query(name)
+    }
+
+    fn append(&mut self, entry: &[String]) -> Result<(), Error> {
+        // a failed append may have left part of its line, it's cut off so
+        // this line doesn't run on from it
+        self.file.set_len(self.len)?;
+
+        let line = serde_json::to_string(entry)? + "\n";
+        self.file.write_all(line.as_bytes())?;
+        self.len += line.len() as u64;
+        Ok(())
+    }
+}
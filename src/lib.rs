@@ -46,8 +46,33 @@ extern crate pest;
 /// ```
 extern crate pest_derive;
 extern crate reqwest;
+extern crate serde;
 extern crate serde_json;
+extern crate sha2;
+mod atomic;
+mod batch;
 mod error;
+mod morphology;
 pub use error::Error;
+pub mod caverphone;
 pub mod cmu;
+pub mod cologne;
+pub mod daitch_mokotoff;
+pub mod devices;
+pub mod dialect;
+pub mod encoder;
+pub mod forms;
+pub mod index;
+pub mod linkage;
+pub mod lyrics;
 pub mod metaphone;
+pub mod nysiis;
+pub mod original_metaphone;
+pub mod phoneme;
+pub mod phonetic;
+pub mod readability;
+pub mod refined_soundex;
+pub mod similarity;
+pub mod soundex;
+pub mod spanish;
+pub mod text;
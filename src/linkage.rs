@@ -0,0 +1,288 @@
+extern crate log;
+
+use encoder::PhoneticCodes;
+use index;
+use metaphone;
+use std::collections::{HashMap, HashSet};
+
+/// Record linkage: find the records that probably name the same person.
+///
+/// Records are blocked by the Double Metaphone codes of each word of their
+/// names, the primary and secondary codes forming overlapping blocks, and only
+/// records sharing a block are compared. Names are compared word by word, each
+/// word paired with a word of the other name it shares a code with, so "Smith,
+/// John" is the same as "John Smith". Pairs scoring at least the threshold are
+/// linked, and linked records are clustered transitively.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::linkage::Linker;
+///
+/// let records = vec![(1, "Jon Smith"), (2, "John Smith"), (3, "Jane Doe"), (4, "Jon Smyth")];
+/// let clusters = Linker::new().cluster(records);
+///
+/// assert_eq!(clusters.len(), 1);
+/// assert_eq!(clusters[0].ids, vec![1, 2, 4]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Linker {
+    threshold: f64,
+    max_block_size: usize,
+}
+
+/// Two records linked as probable duplicates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link<Id> {
+    pub a: Id,
+    pub b: Id,
+    /// Between the linker's threshold and 1.0. Each pair of words sharing a
+    /// code is scored as in `PhoneticIndex::query`, and the words' scores
+    /// summed and scaled by the number of words in both names, so words
+    /// without a partner lower the score.
+    pub score: f64,
+}
+
+/// Records that are probably the same, with the links that joined them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster<Id> {
+    /// In the order the records were given.
+    pub ids: Vec<Id>,
+    pub links: Vec<Link<Id>>,
+}
+
+impl Default for Linker {
+    fn default() -> Self {
+        Linker {
+            threshold: 0.85,
+            max_block_size: 1000,
+        }
+    }
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Linker::default()
+    }
+
+    /// Minimum score for two records to be linked, 0.85 by default.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Blocks with more records than this are skipped, 1000 by default. Very
+    /// common codes make for large blocks that are slow to compare pairwise.
+    /// Each skipped block is logged as a warning with its code and size, its
+    /// records are still compared through the other blocks they're in.
+    pub fn max_block_size(mut self, max_block_size: usize) -> Self {
+        self.max_block_size = max_block_size;
+        self
+    }
+
+    /// Clusters of two or more probable duplicates, in the order of their first
+    /// records.
+    pub fn cluster<Id, S, I>(&self, records: I) -> Vec<Cluster<Id>>
+    where
+        Id: Clone,
+        S: AsRef<str>,
+        I: IntoIterator<Item = (Id, S)>,
+    {
+        let records = records
+            .into_iter()
+            .map(|(id, name)| {
+                let words = words(name.as_ref())
+                    .into_iter()
+                    .map(|w| {
+                        let codes = PhoneticCodes::from(metaphone::encoding(&w));
+                        (w, codes)
+                    })
+                    .collect::<Vec<(String, PhoneticCodes)>>();
+                let blocking =
+                    PhoneticCodes::new(words.iter().flat_map(|(_, codes)| codes.iter().cloned()));
+                (id, words, blocking)
+            })
+            .collect::<Vec<(Id, Vec<(String, PhoneticCodes)>, PhoneticCodes)>>();
+
+        let mut blocks: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, (_, _, blocking)) in records.iter().enumerate() {
+            for code in blocking {
+                blocks.entry(code.as_str()).or_default().push(i);
+            }
+        }
+
+        let mut compared: HashSet<(usize, usize)> = HashSet::new();
+        let mut links: Vec<(usize, usize, f64)> = vec![];
+
+        for (code, block) in &blocks {
+            if block.len() > self.max_block_size {
+                log::warn!(
+                    "skipped the {} block of {} records, more than the maximum of {}",
+                    code,
+                    block.len(),
+                    self.max_block_size
+                );
+                continue;
+            }
+
+            for (n, &i) in block.iter().enumerate() {
+                for &j in &block[n + 1..] {
+                    // records sharing more than one block
+                    if !compared.insert((i, j)) {
+                        continue;
+                    }
+
+                    let score = score(&records[i].1, &records[j].1);
+
+                    if score >= self.threshold {
+                        links.push((i, j, score));
+                    }
+                }
+            }
+        }
+
+        links.sort_by_key(|&(i, j, _)| (i, j));
+
+        let mut sets = DisjointSet::new(records.len());
+        for &(i, j, _) in &links {
+            sets.union(i, j);
+        }
+
+        // every record's set in the order of its first record, then the links
+        // of each set
+        let mut positions: HashMap<usize, usize> = HashMap::new();
+        let mut clusters: Vec<Cluster<Id>> = vec![];
+        for (i, (id, _, _)) in records.iter().enumerate() {
+            let root = sets.find(i);
+            let position = *positions.entry(root).or_insert_with(|| {
+                clusters.push(Cluster {
+                    ids: vec![],
+                    links: vec![],
+                });
+                clusters.len() - 1
+            });
+            clusters[position].ids.push(id.clone());
+        }
+
+        for &(i, j, score) in &links {
+            let position = positions[&sets.find(i)];
+            clusters[position].links.push(Link {
+                a: records[i].0.clone(),
+                b: records[j].0.clone(),
+                score,
+            });
+        }
+
+        clusters.retain(|cluster| cluster.ids.len() > 1);
+        clusters
+    }
+}
+
+/// How alike two names are, word by word. Each word of `a` is paired with the
+/// best scoring word of `b` it shares a code with and that isn't paired yet.
+fn score(a: &[(String, PhoneticCodes)], b: &[(String, PhoneticCodes)]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+
+    let mut paired = vec![false; b.len()];
+    let mut total = 0.0;
+
+    for (word, codes) in a {
+        let best = b
+            .iter()
+            .enumerate()
+            .filter(|&(j, (_, other))| !paired[j] && codes.intersects(other))
+            .map(|(j, (other_word, other))| (j, index::score(word, codes, other_word, other)))
+            .fold(None, |best: Option<(usize, f64)>, (j, score)| match best {
+                Some((_, s)) if s >= score => best,
+                _ => Some((j, score)),
+            });
+
+        if let Some((j, score)) = best {
+            paired[j] = true;
+            total += score;
+        }
+    }
+
+    2.0 * total / (a.len() + b.len()) as f64
+}
+
+/// The lowercase words of a name, without its punctuation.
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|w| w.trim_matches('\'').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Union-find over positions, e.g. of records, with path compression and
+/// union by size.
+pub(crate) struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub(crate) fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+        }
+    }
+
+    /// The root of the set of `i`, pointing every position on the way
+    /// straight at it.
+    pub(crate) fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut i = i;
+        while self.parents[i] != root {
+            let next = self.parents[i];
+            self.parents[i] = root;
+            i = next;
+        }
+
+        root
+    }
+
+    pub(crate) fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+
+        let (small, large) = if self.sizes[a] < self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disjoint_set() {
+        let mut sets = DisjointSet::new(6);
+        sets.union(0, 1);
+        sets.union(2, 3);
+        sets.union(1, 3);
+        sets.union(4, 4);
+
+        let root = sets.find(0);
+        assert!((0..4).all(|i| sets.find(i) == root));
+        assert_ne!(sets.find(4), root);
+        assert_ne!(sets.find(4), sets.find(5));
+
+        // every position points straight at its root once found
+        assert!((0..4).all(|i| sets.parents[i] == root));
+        assert_eq!(sets.sizes[root], 4);
+    }
+}
@@ -0,0 +1,319 @@
+use cmu::{CmuDict, RhymeKey};
+use linkage::DisjointSet;
+use std::ops::Range;
+use text;
+
+/// A word of the analyzed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    pub word: String,
+    /// Byte range in the text.
+    pub span: Range<usize>,
+    /// Index of the line the word is on.
+    pub line: usize,
+}
+
+/// Two words that rhyme, `a` before `b` in the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RhymePair {
+    pub a: Occurrence,
+    pub b: Occurrence,
+    /// The rhyme, ARPAbet symbols without stress from the vowel the rhyme
+    /// starts on, e.g. "IH S T", as compared under the `RhymeOptions` of the
+    /// dictionary.
+    pub sound: String,
+    /// Syllables that rhyme: those of the rhyme itself and the syllables
+    /// before it with the same vowels, possibly in the words before.
+    pub syllables: usize,
+}
+
+/// Words rhyming with each other, directly or through other words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Family {
+    /// The rhyme the words share, see `RhymePair::sound`.
+    pub sound: String,
+    /// In the order of the text.
+    pub words: Vec<Occurrence>,
+    /// The most syllables rhymed between two of the words.
+    pub syllables: usize,
+}
+
+/// The rhymes of a text and how densely it rhymes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub pairs: Vec<RhymePair>,
+    /// In the order they first occur.
+    pub families: Vec<Family>,
+    /// Syllables that are part of a rhyme.
+    pub rhymed_syllables: usize,
+    /// All syllables, guessed from the spelling for words not in the
+    /// dictionary.
+    pub total_syllables: usize,
+    /// Words not in the dictionary, which don't rhyme with anything.
+    pub unknown: Vec<Occurrence>,
+}
+
+impl Analysis {
+    /// Rhymed syllables over all syllables, 0.0 for a text without words.
+    pub fn density(&self) -> f64 {
+        if self.total_syllables == 0 {
+            return 0.0;
+        }
+        self.rhymed_syllables as f64 / self.total_syllables as f64
+    }
+
+    /// The family with the most words linked by rhymes of two or more
+    /// syllables, the earliest of the longest.
+    pub fn longest_chain(&self) -> Option<Family> {
+        let multisyllabic = self
+            .pairs
+            .iter()
+            .filter(|pair| pair.syllables > 1)
+            .cloned()
+            .collect::<Vec<RhymePair>>();
+
+        let mut longest: Option<Family> = None;
+        for family in families(&multisyllabic) {
+            if longest
+                .as_ref()
+                .is_none_or(|longest| family.words.len() > longest.words.len())
+            {
+                longest = Some(family);
+            }
+        }
+        longest
+    }
+}
+
+/// Finds every rhyme in a text, within lines and across them, with the CMU
+/// dictionary. Words rhyme like with `CmuDict::rhyme`, in any of their
+/// pronunciations as said in the dictionary's dialect and compared under its
+/// `RhymeOptions`, and a rhyme extends backwards over the syllables before
+/// with the same vowels, into the words before, making multisyllabic rhymes.
+/// The same word twice isn't a rhyme.
+///
+/// ```rust,no_run
+/// extern crate ttaw;
+/// use ttaw::cmu::CmuDict;
+/// use ttaw::lyrics::Analyzer;
+///
+/// let analyzer = Analyzer::new(CmuDict::new("cmudict.json").unwrap());
+/// let analysis = analyzer.analyze("I'm the type to stay in bed\nwith a pain inside my head");
+///
+/// assert!(analysis.families.iter().any(|family| family.sound == "EH D"));
+/// assert!(analysis.density() > 0.1);
+/// ```
+#[derive(Clone)]
+pub struct Analyzer {
+    cmudict: CmuDict,
+    lines: usize,
+}
+
+impl Analyzer {
+    pub fn new(cmudict: CmuDict) -> Self {
+        Analyzer { cmudict, lines: 2 }
+    }
+
+    /// Most lines between two words that rhyme, 2 by default so the lines of
+    /// couplets and alternating rhymes are paired. 0 for rhymes within lines
+    /// only.
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    pub fn analyze(&self, text: &str) -> Analysis {
+        // the vowel of every syllable of the text, None for the syllables of
+        // unknown words
+        let mut vowels: Vec<Option<&'static str>> = vec![];
+        let mut words: Vec<Word> = vec![];
+        let mut unknown = vec![];
+        let mut line_start = (0, 0);
+        let (mut line, mut counted) = (0, 0);
+
+        for token in text::tokenize(text) {
+            // tokens come in order, so only the text since the last one is new
+            line += text[counted..token.span.start].matches('\n').count();
+            counted = token.span.start;
+            if line != line_start.0 {
+                line_start = (line, vowels.len());
+            }
+
+            let occurrence = Occurrence {
+                word: token.word,
+                span: token.span,
+                line,
+            };
+
+            let pronunciations = match self.cmudict.said_pronunciations(&occurrence.word) {
+                Some(pronunciations) => pronunciations.collect::<Vec<_>>(),
+                None => {
+                    let syllables = text::estimate_syllables(&occurrence.word);
+                    vowels.extend((0..syllables).map(|_| None));
+                    unknown.push(occurrence);
+                    continue;
+                }
+            };
+
+            // the syllables of the first pronunciation
+            let start = vowels.len();
+            vowels.extend(
+                pronunciations[0]
+                    .iter()
+                    .filter(|p| p.is_vowel())
+                    .map(|p| Some(p.base())),
+            );
+            let end = vowels.len();
+
+            // the rhyme of every pronunciation, ending with the word
+            let rhymes = pronunciations
+                .iter()
+                .filter_map(|phones| self.cmudict.rhyme_key(phones))
+                .map(|key| (end.saturating_sub(key.syllables).max(start), key))
+                .collect();
+
+            words.push(Word {
+                key: occurrence.word.to_lowercase(),
+                occurrence,
+                rhymes,
+                end,
+                line_start: line_start.1,
+            });
+        }
+
+        let mut rhymed = vec![false; vowels.len()];
+        let mut pairs = vec![];
+
+        for (i, a) in words.iter().enumerate() {
+            if a.rhymes.is_empty() {
+                continue;
+            }
+
+            for b in &words[i + 1..] {
+                if b.occurrence.line - a.occurrence.line > self.lines {
+                    break;
+                }
+                if a.key == b.key {
+                    continue;
+                }
+
+                let rhyme = a.rhymes.iter().find_map(|(a_start, a_key)| {
+                    b.rhymes
+                        .iter()
+                        .find(|(_, b_key)| a_key == b_key)
+                        .map(|(b_start, _)| (*a_start, *b_start, a_key))
+                });
+                let (a_start, b_start, key) = match rhyme {
+                    Some(rhyme) => rhyme,
+                    None => continue,
+                };
+
+                // the same vowels before the rhyme, within the lines and not
+                // reaching back into the first word's rhyme
+                let mut extra = 0;
+                while a_start - extra > a.line_start
+                    && b_start - extra > b.line_start.max(a.end)
+                    && vowels[a_start - extra - 1].is_some()
+                    && vowels[a_start - extra - 1] == vowels[b_start - extra - 1]
+                {
+                    extra += 1;
+                }
+
+                for syllable in (a_start - extra..a.end).chain(b_start - extra..b.end) {
+                    rhymed[syllable] = true;
+                }
+
+                pairs.push(RhymePair {
+                    a: a.occurrence.clone(),
+                    b: b.occurrence.clone(),
+                    sound: key.sound(),
+                    syllables: a.end - a_start + extra,
+                });
+            }
+        }
+
+        Analysis {
+            families: families(&pairs),
+            pairs,
+            rhymed_syllables: rhymed.iter().filter(|r| **r).count(),
+            total_syllables: vowels.len(),
+            unknown,
+        }
+    }
+}
+
+/// A word of the text with a pronunciation.
+struct Word {
+    occurrence: Occurrence,
+    /// The lowercased word, a word doesn't rhyme with itself.
+    key: String,
+    /// The first syllable and the rhyme of every pronunciation with one.
+    rhymes: Vec<(usize, RhymeKey)>,
+    /// The syllable after the word's last.
+    end: usize,
+    /// The first syllable of the word's line.
+    line_start: usize,
+}
+
+/// Group rhyming words into families.
+fn families<'a>(pairs: &'a [RhymePair]) -> Vec<Family> {
+    // every word of a pair once, and the pairs as positions of the words
+    let mut words: Vec<&Occurrence> = vec![];
+    let mut links = vec![];
+    for pair in pairs {
+        let mut position = |word: &'a Occurrence| match words.iter().position(|w| *w == word) {
+            Some(i) => i,
+            None => {
+                words.push(word);
+                words.len() - 1
+            }
+        };
+        links.push((position(&pair.a), position(&pair.b), pair));
+    }
+
+    let mut sets = DisjointSet::new(words.len());
+    for &(a, b, _) in &links {
+        sets.union(a, b);
+    }
+    let roots = (0..words.len())
+        .map(|i| sets.find(i))
+        .collect::<Vec<usize>>();
+
+    let mut families: Vec<(usize, Family)> = vec![];
+    for (i, word) in words.iter().enumerate() {
+        let root = roots[i];
+        match families.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, family)) => family.words.push((*word).clone()),
+            None => families.push((
+                root,
+                Family {
+                    sound: String::new(),
+                    words: vec![(*word).clone()],
+                    syllables: 0,
+                },
+            )),
+        }
+    }
+
+    for (root, family) in families.iter_mut() {
+        let pairs = links
+            .iter()
+            .filter(|(a, _, _)| roots[*a] == *root)
+            .map(|(_, _, pair)| pair);
+
+        family.words.sort_by_key(|w| w.span.start);
+        for pair in pairs {
+            if family.sound.is_empty() {
+                family.sound = pair.sound.clone();
+            }
+            family.syllables = family.syllables.max(pair.syllables);
+        }
+    }
+
+    let mut families = families
+        .into_iter()
+        .map(|(_, family)| family)
+        .collect::<Vec<Family>>();
+    families.sort_by_key(|family| family.words[0].span.start);
+    families
+}
@@ -1,9 +1,11 @@
-
 extern crate log;
 extern crate pest;
 
+use batch;
+use encoder;
 use pest::Parser;
 use pest_derive::Parser;
+use std::ops::Range;
 
 #[derive(Parser)]
 #[grammar = "grammar.pest"]
@@ -34,6 +36,14 @@ pub struct DoubleMetaphone {
     pub secondary: String,
 }
 
+/// The encoding of a single word in a phrase, see `encode_phrase`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhraseEncoding {
+    /// Byte range of the word in the original text.
+    pub span: Range<usize>,
+    pub encoding: DoubleMetaphone,
+}
+
 /// Use Double Metaphone phonetic encoding to determine if two words rhyme.
 ///
 /// ```rust
@@ -48,10 +58,11 @@ pub struct DoubleMetaphone {
 /// assert!(!ttaw::metaphone::rhyme("run", "uphill"));
 /// ```
 pub fn rhyme(a: &str, b: &str) -> bool {
-    // sanity check, needing to sanity check seems fragile?
-    if a.trim().is_empty() || b.trim().is_empty() {
-        return false;
-    }
+    // phrases rhyme on their last words
+    let (a, b) = match (last_word(a), last_word(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
 
     let a_phonetic = encoding(a);
     let b_phonetic = encoding(b);
@@ -70,33 +81,10 @@ pub fn rhyme(a: &str, b: &str) -> bool {
         b_phonetic.secondary
     );
 
-    let mut a_phonetic_end_primary = a_phonetic.primary;
-    if let Some(slice) = a_phonetic_end_primary.get(1..) {
-        a_phonetic_end_primary = slice.to_string();
-    }
-
-    let mut a_phonetic_end_secondary = a_phonetic.secondary;
-
-    if let Some(slice) = a_phonetic_end_secondary.get(1..) {
-        a_phonetic_end_secondary = slice.to_string();
-    }
-
-    let mut b_phonetic_end_primary = b_phonetic.primary;
-
-    if let Some(slice) = b_phonetic_end_primary.get(1..) {
-        b_phonetic_end_primary = slice.to_string();
-    }
-
-    let mut b_phonetic_end_secondary = b_phonetic.secondary;
-
-    if let Some(slice) = b_phonetic_end_secondary.get(1..) {
-        b_phonetic_end_secondary = slice.to_string();
-    }
-
-    a_phonetic_end_primary == b_phonetic_end_primary
-        || a_phonetic_end_primary == b_phonetic_end_secondary
-        || a_phonetic_end_secondary == b_phonetic_end_primary
-        || a_phonetic_end_secondary == b_phonetic_end_secondary
+    encoder::codes_rhyme(
+        &[a_phonetic.primary, a_phonetic.secondary],
+        &[b_phonetic.primary, b_phonetic.secondary],
+    )
 }
 
 /// Use Double Metaphone phonetic encoding to determine if two words alliterate.
@@ -113,6 +101,12 @@ pub fn rhyme(a: &str, b: &str) -> bool {
 /// assert!(!ttaw::metaphone::alliteration("lazy", "dog"));
 /// ```
 pub fn alliteration(a: &str, b: &str) -> bool {
+    // phrases alliterate on their first words
+    let (a, b) = match (first_word(a), first_word(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+
     if Word::parse(Rule::vowel_first, a.get(..1).unwrap_or_default()).is_ok() {
         return false;
     }
@@ -138,39 +132,55 @@ pub fn alliteration(a: &str, b: &str) -> bool {
         b_phonetic.secondary
     );
 
-    let mut a_phonetic_head_primary = a_phonetic.primary;
-
-    if let Some(c) = a_phonetic_head_primary.get(..1) {
-        a_phonetic_head_primary = c.to_string();
-    }
-
-    let mut a_phonetic_head_secondary = a_phonetic.secondary;
-
-    if let Some(c) = a_phonetic_head_secondary.get(..1) {
-        a_phonetic_head_secondary = c.to_string();
-    }
-
-    let mut b_phonetic_head_primary = b_phonetic.primary;
-
-    if let Some(c) = b_phonetic_head_primary.get(..1) {
-        b_phonetic_head_primary = c.to_string();
-    }
-
-    let mut b_phonetic_head_secondary = b_phonetic.secondary;
-
-    if let Some(c) = b_phonetic_head_secondary.get(..1) {
-        b_phonetic_head_secondary = c.to_string();
-    }
+    encoder::codes_alliterate(
+        &[a_phonetic.primary, a_phonetic.secondary],
+        &[b_phonetic.primary, b_phonetic.secondary],
+    )
+}
 
-    if a_phonetic_head_primary == b_phonetic_head_primary
-        || a_phonetic_head_primary == b_phonetic_head_secondary
-        || a_phonetic_head_secondary == b_phonetic_head_primary
-        || a_phonetic_head_secondary == b_phonetic_head_secondary
-    {
-        return true;
-    }
+/// Double Metaphone phonetic encoding of each word in a phrase, along with the
+/// byte span of the word in the original text. Words are split on whitespace
+/// and punctuation, apostrophes inside a word (e.g. "don't") are kept.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// let phrase = ttaw::metaphone::encode_phrase("Arnow, detestable!");
+/// assert_eq!(phrase.len(), 2);
+///
+/// assert_eq!(phrase[0].span, 0..5);
+/// assert_eq!(phrase[0].encoding.primary, "ARN");
+///
+/// assert_eq!(phrase[1].span, 7..17);
+/// assert_eq!(phrase[1].encoding.primary, "TTSTPL");
+/// ```
+pub fn encode_phrase(text: &str) -> Vec<PhraseEncoding> {
+    words(text)
+        .into_iter()
+        .map(|span| PhraseEncoding {
+            encoding: encoding(&text[span.clone()]),
+            span,
+        })
+        .collect()
+}
 
-    false
+/// Double Metaphone phonetic encoding of many words, in the order given. With
+/// the `parallel` feature the words are encoded across threads.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// let encodings = ttaw::metaphone::encode_batch(vec!["Arnow", "detestable"]);
+/// assert_eq!(encodings[0].primary, "ARN");
+/// assert_eq!(encodings[1].primary, "TTSTPL");
+/// ```
+pub fn encode_batch<I, S>(words: I) -> Vec<DoubleMetaphone>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str> + Sync,
+{
+    let words = words.into_iter().collect::<Vec<S>>();
+    batch::map(&words, |word| encoding(word.as_ref()))
 }
 
 /// Double Metaphone phonetic encoding.
@@ -305,6 +315,48 @@ pub fn encoding(input: &str) -> DoubleMetaphone {
     }
 }
 
+/// Byte spans of the words in `text`. Anything that isn't alphanumeric
+/// separates words, except an apostrophe with alphanumerics on both sides.
+fn words(text: &str) -> Vec<Range<usize>> {
+    let mut spans = vec![];
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let in_word = c.is_alphanumeric()
+            || (is_apostrophe(c)
+                && start.is_some()
+                && chars.peek().is_some_and(|(_, n)| n.is_alphanumeric()));
+
+        match (in_word, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        spans.push(s..text.len());
+    }
+
+    spans
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+fn first_word(text: &str) -> Option<&str> {
+    words(text).into_iter().next().map(|span| &text[span])
+}
+
+fn last_word(text: &str) -> Option<&str> {
+    words(text).pop().map(|span| &text[span])
+}
+
 fn get_char_as_string(chars: &[char], pos: usize) -> String {
     match chars.get(pos) {
         Some(c) => c.to_string(),
@@ -1361,4 +1413,4 @@ mod tests {
         word = "orchid".to_uppercase();
         assert!(Word::parse(Rule::greek_ch, word.as_str()).is_ok());
     }
-}
\ No newline at end of file
+}
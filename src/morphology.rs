@@ -0,0 +1,217 @@
+use phoneme::Phoneme;
+use text;
+
+/// An English inflectional or derivational suffix, pronounced after a stem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Suffix {
+    /// Plurals, possessives and third person "-s"/"-es".
+    S,
+    Ed,
+    Ing,
+    Er,
+    Ly,
+    Ness,
+}
+
+/// Spellings of each suffix, tried in order.
+const SPELLINGS: &[(&str, Suffix)] = &[
+    ("'s", Suffix::S),
+    ("ness", Suffix::Ness),
+    ("ing", Suffix::Ing),
+    ("ed", Suffix::Ed),
+    ("er", Suffix::Er),
+    ("ly", Suffix::Ly),
+    ("es", Suffix::S),
+    ("s", Suffix::S),
+];
+
+impl Suffix {
+    /// The suffix after a stem ending in `last`, with the allomorph the stem
+    /// calls for: "IH0 Z" after a sibilant, "S" after another voiceless
+    /// consonant and "Z" otherwise; "IH0 D" after "T" or "D", "T" after
+    /// another voiceless consonant and "D" otherwise.
+    pub(crate) fn phonemes(self, last: Option<Phoneme>) -> Vec<Phoneme> {
+        let last = last.map(Phoneme::base);
+        let symbols: &[&str] = match self {
+            Suffix::S if is_sibilant(last) => &["IH0", "Z"],
+            Suffix::S if is_voiceless(last) => &["S"],
+            Suffix::S => &["Z"],
+            Suffix::Ed if last == Some("T") || last == Some("D") => &["IH0", "D"],
+            Suffix::Ed if is_voiceless(last) || is_voiceless_sibilant(last) => &["T"],
+            Suffix::Ed => &["D"],
+            Suffix::Ing => &["IH0", "NG"],
+            Suffix::Er => &["ER0"],
+            Suffix::Ly => &["L", "IY0"],
+            Suffix::Ness => &["N", "AH0", "S"],
+        };
+
+        symbols.iter().filter_map(|s| s.parse().ok()).collect()
+    }
+}
+
+pub(crate) fn ends_in_sibilant(phones: &[Phoneme]) -> bool {
+    is_sibilant(phones.last().map(|p| p.base()))
+}
+
+fn is_sibilant(base: Option<&str>) -> bool {
+    matches!(
+        base,
+        Some("S") | Some("Z") | Some("SH") | Some("ZH") | Some("CH") | Some("JH")
+    )
+}
+
+fn is_voiceless_sibilant(base: Option<&str>) -> bool {
+    matches!(base, Some("S") | Some("SH") | Some("CH"))
+}
+
+fn is_voiceless(base: Option<&str>) -> bool {
+    matches!(
+        base,
+        Some("P") | Some("T") | Some("K") | Some("F") | Some("TH")
+    )
+}
+
+/// A candidate stem of an inflected word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Stem {
+    pub(crate) word: String,
+    pub(crate) suffix: Suffix,
+    /// Only a stem ending in a sibilant takes the spelled "-es" as is ("foxes"
+    /// but not "goes" from "go").
+    pub(crate) sibilant: bool,
+}
+
+/// The fewest letters in a stem.
+const MIN_STEM: usize = 3;
+
+/// Candidate stems of an inflected word, most likely first, undoing spelling
+/// changes: "baked" from "bake", "running" from "run" and "happiness" from
+/// "happy". Stems are at least `MIN_STEM` letters with a vowel among them, so
+/// "bly" isn't "b" with "-ly"; whether a stem is a word is left to the
+/// dictionary.
+pub(crate) fn stems(word: &str) -> Vec<Stem> {
+    let mut stems = vec![];
+
+    for &(ending, suffix) in SPELLINGS {
+        let base = match word.strip_suffix(ending) {
+            Some(base) if !base.is_empty() && !base.ends_with('\'') => base,
+            _ => continue,
+        };
+
+        let stem = |word: String| Stem {
+            word,
+            suffix,
+            sibilant: false,
+        };
+
+        stems.push(Stem {
+            sibilant: ending == "es",
+            ..stem(base.to_string())
+        });
+
+        if let Some(base) = base.strip_suffix('i') {
+            stems.push(stem(format!("{}y", base)));
+        }
+
+        // the "e" of the stem is dropped before a vowel and the last
+        // consonant doubled
+        if ending.starts_with('e') || ending == "ing" {
+            stems.push(stem(format!("{}e", base)));
+
+            let mut chars = base.chars().rev();
+            if let (Some(a), Some(b)) = (chars.next(), chars.next()) {
+                if a == b && !"aeiouy".contains(a) {
+                    stems.push(stem(base[..base.len() - a.len_utf8()].to_string()));
+                }
+            }
+        }
+    }
+
+    stems.retain(|stem| is_stem(&stem.word));
+    stems
+}
+
+fn is_stem(word: &str) -> bool {
+    word.chars().count() >= MIN_STEM && word.chars().any(|c| "aeiouy".contains(c))
+}
+
+/// The fewest letters in each part of a compound.
+const MIN_COMPOUND_PART: usize = 3;
+
+/// Splits of a compound into two parts of at least three letters, neither a
+/// function word, the longest first part first. A hyphen between the parts is
+/// dropped.
+pub(crate) fn compound_splits(word: &str) -> Vec<(&str, &str)> {
+    let part = |p: &str| p.chars().count() >= MIN_COMPOUND_PART && !text::is_function_word(p);
+
+    word.char_indices()
+        .map(|(i, _)| i)
+        .rev()
+        .map(|i| word.split_at(i))
+        .map(|(a, b)| (a.trim_end_matches('-'), b.trim_start_matches('-')))
+        .filter(|(a, b)| part(a) && part(b))
+        .collect()
+}
+
+/// A phoneme with primary stress demoted to secondary, for the second part of
+/// a compound ("blackbird" is "B L AE1 K B ER2 D").
+pub(crate) fn demote(phoneme: Phoneme) -> Phoneme {
+    if phoneme.stress() != Some(1) {
+        return phoneme;
+    }
+
+    format!("{}2", phoneme.base()).parse().unwrap_or(phoneme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(suffix: Suffix, last: &str) -> String {
+        suffix
+            .phonemes(last.parse().ok())
+            .iter()
+            .map(|p| p.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn allomorphs() {
+        assert_eq!(symbols(Suffix::S, "K"), "S");
+        assert_eq!(symbols(Suffix::S, "G"), "Z");
+        assert_eq!(symbols(Suffix::S, "IY1"), "Z");
+        assert_eq!(symbols(Suffix::S, "CH"), "IH0 Z");
+        assert_eq!(symbols(Suffix::Ed, "T"), "IH0 D");
+        assert_eq!(symbols(Suffix::Ed, "K"), "T");
+        assert_eq!(symbols(Suffix::Ed, "SH"), "T");
+        assert_eq!(symbols(Suffix::Ed, "Z"), "D");
+        assert_eq!(symbols(Suffix::Ed, "N"), "D");
+        assert_eq!(symbols(Suffix::Ing, "N"), "IH0 NG");
+    }
+
+    #[test]
+    fn spelling_changes() {
+        let stems = |word| {
+            stems(word)
+                .into_iter()
+                .map(|stem| stem.word)
+                .collect::<Vec<String>>()
+        };
+
+        assert!(stems("baked").contains(&"bake".to_string()));
+        assert!(stems("running").contains(&"run".to_string()));
+        assert!(stems("happiness").contains(&"happy".to_string()));
+        assert!(stems("cities").contains(&"city".to_string()));
+        assert_eq!(stems("dog's"), vec!["dog"]);
+        assert!(stems("dog").is_empty());
+        assert!(stems("bly").is_empty());
+        assert!(stems("xed").is_empty());
+
+        assert!(compound_splits("well-known").contains(&("well", "known")));
+        assert_eq!(compound_splits("abcdef"), vec![("abc", "def")]);
+        assert!(compound_splits("abcde").is_empty());
+        // "for" is a function word
+        assert!(!compound_splits("forget").contains(&("for", "get")));
+    }
+}
@@ -0,0 +1,126 @@
+use soundex::letters;
+
+/// New York State Identification and Intelligence System (NYSIIS) phonetic
+/// encoding, truncated to the original six characters.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::nysiis::encoding("Knuth"), "NAT");
+/// assert_eq!(ttaw::nysiis::encoding("Robertson"), "RABART");
+/// ```
+pub fn encoding(input: &str) -> String {
+    let mut code = full_encoding(input);
+    code.truncate(6);
+    code
+}
+
+/// NYSIIS phonetic encoding without the six character limit.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::nysiis::full_encoding("Robertson"), "RABARTSAN");
+/// ```
+pub fn full_encoding(input: &str) -> String {
+    let word = letters(input).into_iter().collect::<String>();
+
+    if word.is_empty() {
+        return String::new();
+    }
+
+    let mut chars = translate_suffix(&translate_prefix(&word))
+        .chars()
+        .collect::<Vec<char>>();
+
+    let mut code = chars[0].to_string();
+
+    for pos in 1..chars.len() {
+        let next = chars.get(pos + 1).cloned().unwrap_or(' ');
+        let after_next = chars.get(pos + 2).cloned().unwrap_or(' ');
+        let translated = translate(chars[pos - 1], chars[pos], next, after_next);
+
+        // multi letter translations overwrite the letters that follow
+        for (i, c) in translated.chars().enumerate() {
+            if let Some(slot) = chars.get_mut(pos + i) {
+                *slot = c;
+            }
+        }
+
+        if chars[pos] != chars[pos - 1] {
+            code.push(chars[pos]);
+        }
+    }
+
+    if code.len() > 1 {
+        if code.ends_with('S') {
+            code.pop();
+        }
+
+        if code.len() > 2 && code.ends_with("AY") {
+            code.remove(code.len() - 2);
+        }
+
+        if code.ends_with('A') {
+            code.pop();
+        }
+    }
+
+    code
+}
+
+fn translate_prefix(word: &str) -> String {
+    for (from, to) in &[
+        ("MAC", "MCC"),
+        ("KN", "NN"),
+        ("K", "C"),
+        ("PH", "FF"),
+        ("PF", "FF"),
+        ("SCH", "SSS"),
+    ] {
+        if let Some(rest) = word.strip_prefix(from) {
+            return format!("{}{}", to, rest);
+        }
+    }
+
+    word.to_string()
+}
+
+fn translate_suffix(word: &str) -> String {
+    for (from, to) in &[
+        ("EE", "Y"),
+        ("IE", "Y"),
+        ("DT", "D"),
+        ("RT", "D"),
+        ("RD", "D"),
+        ("NT", "D"),
+        ("ND", "D"),
+    ] {
+        if let Some(rest) = word.strip_suffix(from) {
+            return format!("{}{}", rest, to);
+        }
+    }
+
+    word.to_string()
+}
+
+fn translate(prev: char, c: char, next: char, after_next: char) -> String {
+    match c {
+        'E' if next == 'V' => "AF".to_string(),
+        c if is_vowel(c) => "A".to_string(),
+        'Q' => "G".to_string(),
+        'Z' => "S".to_string(),
+        'M' => "N".to_string(),
+        'K' if next == 'N' => "NN".to_string(),
+        'K' => "C".to_string(),
+        'S' if next == 'C' && after_next == 'H' => "SSS".to_string(),
+        'P' if next == 'H' => "FF".to_string(),
+        'H' if !is_vowel(prev) || !is_vowel(next) => prev.to_string(),
+        'W' if is_vowel(prev) => prev.to_string(),
+        c => c.to_string(),
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U')
+}
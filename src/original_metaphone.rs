@@ -0,0 +1,144 @@
+use soundex::letters;
+
+/// Original (single) Metaphone phonetic encoding. `0` stands in for "th".
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::original_metaphone::encoding("knight"), "NT");
+/// assert_eq!(ttaw::original_metaphone::encoding("Xavier"), "SFR");
+/// assert_eq!(ttaw::original_metaphone::encoding("thumb"), "0M");
+/// ```
+pub fn encoding(input: &str) -> String {
+    let mut chars = letters(input);
+
+    match chars.as_slice() {
+        [] => return String::new(),
+        ['A', 'E', ..] | ['G', 'N', ..] | ['K', 'N', ..] | ['P', 'N', ..] | ['W', 'R', ..] => {
+            chars.remove(0);
+        }
+        ['W', 'H', ..] => {
+            chars.remove(1);
+        }
+        ['X', ..] => chars[0] = 'S',
+        _ => {}
+    }
+
+    let at = |i: usize| chars.get(i).cloned().unwrap_or(' ');
+    let matches_at = |i: usize, s: &str| s.chars().enumerate().all(|(j, c)| at(i + j) == c);
+    let is_vowel = |i: usize| matches!(at(i), 'A' | 'E' | 'I' | 'O' | 'U');
+    let is_front_vowel = |i: usize| matches!(at(i), 'E' | 'I' | 'Y');
+    let prev = |i: usize| if i > 0 { at(i - 1) } else { ' ' };
+    let last = chars.len() - 1;
+
+    let mut code = String::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+
+        // doubled letters are only coded once, except C
+        if c != 'C' && prev(pos) == c {
+            pos += 1;
+            continue;
+        }
+
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' if pos == 0 => code.push(c),
+
+            // silent in a final "MB"
+            'B' if !(prev(pos) == 'M' && pos == last) => code.push('B'),
+
+            'C' => {
+                if prev(pos) == 'S' && is_front_vowel(pos + 1) {
+                    // silent in "SCE", "SCI" and "SCY"
+                } else if matches_at(pos, "CIA") {
+                    code.push('X');
+                } else if is_front_vowel(pos + 1) {
+                    code.push('S');
+                } else if prev(pos) == 'S' && at(pos + 1) == 'H' {
+                    code.push('K');
+                } else if at(pos + 1) == 'H' {
+                    // initial "CH" before a consonant, as in "Christ"
+                    if pos == 0 && chars.len() >= 3 && !is_vowel(2) {
+                        code.push('K');
+                    } else {
+                        code.push('X');
+                    }
+                } else {
+                    code.push('K');
+                }
+            }
+
+            'D' => {
+                if at(pos + 1) == 'G' && is_front_vowel(pos + 2) {
+                    code.push('J');
+                    pos += 2;
+                } else {
+                    code.push('T');
+                }
+            }
+
+            'G' => {
+                if at(pos + 1) == 'H' && (pos + 1 == last || !is_vowel(pos + 2)) {
+                    // silent in "GH" unless a vowel follows
+                } else if pos > 0 && matches_at(pos, "GN") {
+                    // silent in "GN" and "GNED"
+                } else if is_front_vowel(pos + 1) && prev(pos) != 'G' {
+                    code.push('J');
+                } else {
+                    code.push('K');
+                }
+            }
+
+            'H' if pos != last
+                && !matches!(prev(pos), 'C' | 'S' | 'P' | 'T' | 'G')
+                && is_vowel(pos + 1) =>
+            {
+                code.push('H')
+            }
+
+            'F' | 'J' | 'L' | 'M' | 'N' | 'R' => code.push(c),
+
+            'K' if prev(pos) != 'C' => code.push('K'),
+
+            'P' => code.push(if at(pos + 1) == 'H' { 'F' } else { 'P' }),
+
+            'Q' => code.push('K'),
+
+            'S' => {
+                if matches_at(pos, "SH") || matches_at(pos, "SIO") || matches_at(pos, "SIA") {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+
+            'T' => {
+                if matches_at(pos, "TIA") || matches_at(pos, "TIO") {
+                    code.push('X');
+                } else if matches_at(pos, "TCH") {
+                    // silent, the "CH" is coded
+                } else if at(pos + 1) == 'H' {
+                    code.push('0');
+                } else {
+                    code.push('T');
+                }
+            }
+
+            'V' => code.push('F'),
+
+            'W' | 'Y' if is_vowel(pos + 1) => code.push(c),
+
+            'X' => code.push_str("KS"),
+
+            'Z' => code.push('S'),
+
+            _ => {}
+        }
+
+        pos += 1;
+    }
+
+    code
+}
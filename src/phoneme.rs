@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use error::Error;
+
+/// An ARPAbet phoneme as used by CMUdict, with its lexical stress for vowels
+/// (e.g. "AH0", "IY1"). Stored as a single byte.
+///
+/// Pronunciations changed by a `Dialect` can also hold "OH", the rounded
+/// vowel of "lot" in British English. It isn't ARPAbet, so it's never parsed
+/// and never appears in a dictionary.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::phoneme::Phoneme;
+///
+/// let phoneme: Phoneme = "IY1".parse().unwrap();
+/// assert_eq!(phoneme.as_str(), "IY1");
+/// assert_eq!(phoneme.base(), "IY");
+/// assert_eq!(phoneme.stress(), Some(1));
+/// assert!(phoneme.is_vowel());
+/// assert!("XX".parse::<Phoneme>().is_err());
+/// assert!("OH1".parse::<Phoneme>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Phoneme(u8);
+
+const CONSONANTS: &[&str] = &[
+    "B", "CH", "D", "DH", "F", "G", "HH", "JH", "K", "L", "M", "N", "NG", "P", "R", "S", "SH", "T",
+    "TH", "V", "W", "Y", "Z", "ZH",
+];
+
+/// Every phoneme symbol, indexed by code: consonants, then each vowel without
+/// stress marker followed by its stress 0, 1 and 2 forms. "OH", the vowel of
+/// "lot" in British English, isn't in CMUdict and comes last, so dialects can
+/// tell it from "AA".
+static SYMBOLS: &[&str] = &[
+    "B", "CH", "D", "DH", "F", "G", "HH", "JH", "K", "L", "M", "N", "NG", "P", "R", "S", "SH", "T",
+    "TH", "V", "W", "Y", "Z", "ZH", "AA", "AA0", "AA1", "AA2", "AE", "AE0", "AE1", "AE2", "AH",
+    "AH0", "AH1", "AH2", "AO", "AO0", "AO1", "AO2", "AW", "AW0", "AW1", "AW2", "AY", "AY0", "AY1",
+    "AY2", "EH", "EH0", "EH1", "EH2", "ER", "ER0", "ER1", "ER2", "EY", "EY0", "EY1", "EY2", "IH",
+    "IH0", "IH1", "IH2", "IY", "IY0", "IY1", "IY2", "OW", "OW0", "OW1", "OW2", "OY", "OY0", "OY1",
+    "OY2", "UH", "UH0", "UH1", "UH2", "UW", "UW0", "UW1", "UW2", "OH", "OH0", "OH1", "OH2",
+];
+
+/// The number of ARPAbet symbols, every symbol but "OH".
+const ARPABET: usize = CONSONANTS.len() + 15 * 4;
+
+/// The phoneme of any symbol, "OH" included.
+fn lookup(symbol: &str) -> Option<Phoneme> {
+    static CODES: OnceLock<HashMap<&'static str, u8>> = OnceLock::new();
+
+    let codes = CODES.get_or_init(|| {
+        SYMBOLS
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (*s, i as u8))
+            .collect()
+    });
+
+    codes.get(symbol).map(|code| Phoneme(*code))
+}
+
+impl Phoneme {
+    /// The ARPAbet symbol, with its stress marker.
+    pub fn as_str(self) -> &'static str {
+        SYMBOLS[self.0 as usize]
+    }
+
+    /// The symbol without its stress marker.
+    pub fn base(self) -> &'static str {
+        self.as_str().trim_end_matches(|c: char| c.is_ascii_digit())
+    }
+
+    /// Lexical stress of a vowel: 0 unstressed, 1 primary, 2 secondary.
+    pub fn stress(self) -> Option<u8> {
+        self.as_str()
+            .chars()
+            .last()
+            .and_then(|c| c.to_digit(10))
+            .map(|d| d as u8)
+    }
+
+    pub fn is_vowel(self) -> bool {
+        self.0 as usize >= CONSONANTS.len()
+    }
+
+    /// The byte code of the phoneme.
+    pub fn code(self) -> u8 {
+        self.0
+    }
+
+    /// The vowel `base` with `stress`, for dialects, which can make "OH".
+    pub(crate) fn vowel(base: &str, stress: u8) -> Option<Phoneme> {
+        lookup(&format!("{}{}", base, stress)).filter(|p| p.is_vowel())
+    }
+}
+
+impl FromStr for Phoneme {
+    type Err = Error;
+
+    /// Parse an ARPAbet symbol.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lookup(s)
+            .filter(|p| (p.0 as usize) < ARPABET)
+            .ok_or_else(|| Error::InvalidPhoneme(s.to_string()))
+    }
+}
+
+impl fmt::Display for Phoneme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol_table() {
+        assert_eq!(SYMBOLS.len(), CONSONANTS.len() + 16 * 4);
+
+        for (i, symbol) in SYMBOLS.iter().enumerate() {
+            let phoneme = lookup(symbol).unwrap();
+            assert_eq!(phoneme.code() as usize, i);
+            assert_eq!(symbol.parse::<Phoneme>().is_ok(), i < ARPABET);
+
+            let stressed = format!("{}1", phoneme.base());
+            assert_eq!(phoneme.is_vowel(), SYMBOLS.contains(&stressed.as_str()));
+        }
+    }
+}
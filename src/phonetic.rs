@@ -0,0 +1,304 @@
+use cmu::CmuDict;
+use encoder::{Encoder, PhoneticCodes};
+use error::Error;
+use metaphone;
+
+/// A backend that can decide whether two words rhyme.
+pub trait RhymeChecker {
+    fn rhyme(&self, a: &str, b: &str) -> Result<bool, Error>;
+
+    /// Whether two words rhyme, or which of them the backend doesn't know.
+    /// Defaults to the answer from `rhyme`, for backends that know every word.
+    fn rhyme_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        Ok(self.rhyme(a, b)?.into())
+    }
+}
+
+/// A backend that can decide whether two words alliterate.
+pub trait AlliterationChecker {
+    fn alliteration(&self, a: &str, b: &str) -> Result<bool, Error>;
+
+    /// Whether two words alliterate, or which of them the backend doesn't
+    /// know. Defaults to the answer from `alliteration`.
+    fn alliteration_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        Ok(self.alliteration(a, b)?.into())
+    }
+}
+
+/// The answer of a `RhymeChecker` or `AlliterationChecker`.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::phonetic::Verdict;
+///
+/// let verdict = Verdict::Fallback {
+///     missing: vec!["Kowalski".to_string()],
+///     verdict: Box::new(Verdict::Yes),
+/// };
+/// assert!(verdict.is_yes());
+/// assert_eq!(verdict.missing(), ["Kowalski"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Yes,
+    No,
+    /// The backend doesn't know the `missing` words, so it can't tell.
+    Unknown {
+        missing: Vec<String>,
+    },
+    /// The primary backend of a `Chain` didn't know the `missing` words, the
+    /// fallback backend gave the `verdict`.
+    Fallback {
+        missing: Vec<String>,
+        verdict: Box<Verdict>,
+    },
+    /// The backend doesn't have the `derived` words themselves, it gave the
+    /// `verdict` with pronunciations derived from their stems or parts.
+    Derived {
+        derived: Vec<String>,
+        verdict: Box<Verdict>,
+    },
+}
+
+impl Verdict {
+    /// Whether the answer is yes, from the backend or its fallback.
+    pub fn is_yes(&self) -> bool {
+        match self {
+            Verdict::Yes => true,
+            Verdict::Fallback { verdict, .. } | Verdict::Derived { verdict, .. } => {
+                verdict.is_yes()
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether no backend could answer.
+    pub fn is_unknown(&self) -> bool {
+        match self {
+            Verdict::Unknown { .. } => true,
+            Verdict::Fallback { verdict, .. } => verdict.is_unknown(),
+            _ => false,
+        }
+    }
+
+    /// The words the (primary) backend didn't know.
+    pub fn missing(&self) -> &[String] {
+        match self {
+            Verdict::Unknown { missing } | Verdict::Fallback { missing, .. } => missing,
+            _ => &[],
+        }
+    }
+
+    /// The words the backend, or its fallback, derived pronunciations for.
+    pub fn derived(&self) -> &[String] {
+        match self {
+            Verdict::Derived { derived, .. } => derived,
+            Verdict::Fallback { verdict, .. } => verdict.derived(),
+            _ => &[],
+        }
+    }
+}
+
+impl From<bool> for Verdict {
+    fn from(yes: bool) -> Self {
+        if yes {
+            Verdict::Yes
+        } else {
+            Verdict::No
+        }
+    }
+}
+
+/// The Double Metaphone backend, it can encode any word.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::encoder::Encoder;
+/// use ttaw::phonetic::{Metaphone, RhymeChecker};
+/// assert!(Metaphone.rhyme("Far", "Tar").unwrap());
+/// assert_eq!(Metaphone.encode_all("Arnow").to_vec(), vec!["ARN", "ARNF"]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Metaphone;
+
+impl Encoder for Metaphone {
+    fn encode(&self, word: &str) -> String {
+        metaphone::encoding(word).primary
+    }
+
+    fn encode_all(&self, word: &str) -> PhoneticCodes {
+        PhoneticCodes::from(metaphone::encoding(word))
+    }
+}
+
+impl RhymeChecker for Metaphone {
+    fn rhyme(&self, a: &str, b: &str) -> Result<bool, Error> {
+        Ok(metaphone::rhyme(a, b))
+    }
+}
+
+impl AlliterationChecker for Metaphone {
+    fn alliteration(&self, a: &str, b: &str) -> Result<bool, Error> {
+        Ok(metaphone::alliteration(a, b))
+    }
+}
+
+impl Encoder for CmuDict {
+    /// The first pronunciation of a word, its phonemes joined with spaces.
+    /// Empty when the word isn't in the dictionary.
+    fn encode(&self, word: &str) -> String {
+        self.encode_all(word)
+            .primary()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Every pronunciation of a word, each with its phonemes joined with
+    /// spaces.
+    fn encode_all(&self, word: &str) -> PhoneticCodes {
+        let pronunciations = self.pronunciations(word).into_iter().flatten();
+        PhoneticCodes::new(pronunciations.map(|p| {
+            p.iter()
+                .map(|phoneme| phoneme.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ")
+        }))
+    }
+}
+
+impl RhymeChecker for CmuDict {
+    fn rhyme(&self, a: &str, b: &str) -> Result<bool, Error> {
+        CmuDict::rhyme(self, a, b)
+    }
+
+    fn rhyme_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        CmuDict::rhyme_verdict(self, a, b)
+    }
+}
+
+impl AlliterationChecker for CmuDict {
+    fn alliteration(&self, a: &str, b: &str) -> Result<bool, Error> {
+        CmuDict::alliteration(self, a, b)
+    }
+
+    fn alliteration_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        CmuDict::alliteration_verdict(self, a, b)
+    }
+}
+
+impl<T: RhymeChecker + ?Sized> RhymeChecker for &T {
+    fn rhyme(&self, a: &str, b: &str) -> Result<bool, Error> {
+        (**self).rhyme(a, b)
+    }
+
+    fn rhyme_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        (**self).rhyme_verdict(a, b)
+    }
+}
+
+impl<T: AlliterationChecker + ?Sized> AlliterationChecker for &T {
+    fn alliteration(&self, a: &str, b: &str) -> Result<bool, Error> {
+        (**self).alliteration(a, b)
+    }
+
+    fn alliteration_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        (**self).alliteration_verdict(a, b)
+    }
+}
+
+/// Use the `primary` backend when it knows both words and the `fallback`
+/// backend otherwise, e.g. the CMU dictionary falling back to metaphone for
+/// names and other words missing from the dictionary. Verdicts from the
+/// fallback are reported as `Verdict::Fallback`.
+///
+/// ```rust,no_run
+/// extern crate ttaw;
+/// use ttaw::cmu::CmuDict;
+/// use ttaw::phonetic::{Chain, Metaphone, RhymeChecker};
+///
+/// let cmudict = CmuDict::new("cmudict.json").unwrap();
+/// let checker = Chain::new(&cmudict, Metaphone);
+/// assert!(checker.rhyme("hissed", "mist").unwrap());
+/// // "Kowalski" isn't in CMUdict, so metaphone decides
+/// assert!(checker.rhyme("Kowalski", "Nowalski").unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Chain<A, B> {
+    primary: A,
+    fallback: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn new(primary: A, fallback: B) -> Self {
+        Chain { primary, fallback }
+    }
+
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    pub fn fallback(&self) -> &B {
+        &self.fallback
+    }
+}
+
+impl<A: Encoder, B: Encoder> Encoder for Chain<A, B> {
+    /// The primary backend's key of a word, or the fallback's when the
+    /// primary can't encode it.
+    fn encode(&self, word: &str) -> String {
+        self.encode_all(word)
+            .primary()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn encode_all(&self, word: &str) -> PhoneticCodes {
+        let codes = self.primary.encode_all(word);
+        if codes.is_empty() {
+            return self.fallback.encode_all(word);
+        }
+        codes
+    }
+
+    /// Whether two words share a code, from the primary backend when it can
+    /// encode both, so codes of the two backends are never compared.
+    fn is_match(&self, a: &str, b: &str) -> bool {
+        let (codes_a, codes_b) = (self.primary.encode_all(a), self.primary.encode_all(b));
+        if codes_a.is_empty() || codes_b.is_empty() {
+            return self.fallback.is_match(a, b);
+        }
+        codes_a.intersects(&codes_b)
+    }
+}
+
+impl<A: RhymeChecker, B: RhymeChecker> RhymeChecker for Chain<A, B> {
+    fn rhyme(&self, a: &str, b: &str) -> Result<bool, Error> {
+        Ok(self.rhyme_verdict(a, b)?.is_yes())
+    }
+
+    fn rhyme_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        match self.primary.rhyme_verdict(a, b)? {
+            Verdict::Unknown { missing } => Ok(Verdict::Fallback {
+                missing,
+                verdict: Box::new(self.fallback.rhyme_verdict(a, b)?),
+            }),
+            verdict => Ok(verdict),
+        }
+    }
+}
+
+impl<A: AlliterationChecker, B: AlliterationChecker> AlliterationChecker for Chain<A, B> {
+    fn alliteration(&self, a: &str, b: &str) -> Result<bool, Error> {
+        Ok(self.alliteration_verdict(a, b)?.is_yes())
+    }
+
+    fn alliteration_verdict(&self, a: &str, b: &str) -> Result<Verdict, Error> {
+        match self.primary.alliteration_verdict(a, b)? {
+            Verdict::Unknown { missing } => Ok(Verdict::Fallback {
+                missing,
+                verdict: Box::new(self.fallback.alliteration_verdict(a, b)?),
+            }),
+            verdict => Ok(verdict),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use cmu::CmuDict;
+use std::collections::HashSet;
+use text::{self, Token};
+
+/// Counts of a text the readability formulas are computed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Readability {
+    pub sentences: usize,
+    pub words: usize,
+    pub syllables: usize,
+    /// Words of three or more syllables.
+    pub polysyllables: usize,
+    /// Words of three or more syllables that aren't proper nouns and don't
+    /// reach three only with an "-es", "-ed" or "-ing" ending, as the Gunning
+    /// Fog index counts them.
+    pub complex_words: usize,
+    /// Words not in the dictionary, their syllables guessed from the spelling.
+    pub estimated: Vec<Token>,
+}
+
+impl Readability {
+    /// Flesch Reading Ease, higher is easier, 60 to 70 being plain English.
+    /// 0.0 for a text without words.
+    pub fn flesch_reading_ease(&self) -> f64 {
+        if self.words == 0 {
+            return 0.0;
+        }
+        206.835 - 1.015 * self.words_per_sentence() - 84.6 * self.syllables_per_word()
+    }
+
+    /// Flesch-Kincaid grade level, the US school grade needed to read the
+    /// text. 0.0 for a text without words.
+    pub fn flesch_kincaid_grade(&self) -> f64 {
+        if self.words == 0 {
+            return 0.0;
+        }
+        0.39 * self.words_per_sentence() + 11.8 * self.syllables_per_word() - 15.59
+    }
+
+    /// Gunning Fog index, the years of education needed to read the text.
+    /// 0.0 for a text without words.
+    pub fn gunning_fog(&self) -> f64 {
+        if self.words == 0 {
+            return 0.0;
+        }
+        0.4 * (self.words_per_sentence() + 100.0 * self.complex_words as f64 / self.words as f64)
+    }
+
+    /// SMOG grade, scaled to 30 sentences for shorter texts. 0.0 for a text
+    /// without words.
+    pub fn smog(&self) -> f64 {
+        if self.words == 0 {
+            return 0.0;
+        }
+        1.043 * (self.polysyllables as f64 * 30.0 / self.sentences as f64).sqrt() + 3.1291
+    }
+
+    fn words_per_sentence(&self) -> f64 {
+        self.words as f64 / self.sentences as f64
+    }
+
+    fn syllables_per_word(&self) -> f64 {
+        self.syllables as f64 / self.words as f64
+    }
+}
+
+/// Scores the readability of texts, counting syllables with the CMU
+/// dictionary when it's given and knows the word, and from the spelling
+/// otherwise. Numbers and abbreviations count as the words they're read as.
+///
+/// ```rust,no_run
+/// extern crate ttaw;
+/// use ttaw::cmu::CmuDict;
+/// use ttaw::readability::Scorer;
+///
+/// let scorer = Scorer::new().cmudict(CmuDict::new("cmudict.json").unwrap());
+/// let readability = scorer.score("The cat sat on the mat. It was happy.");
+///
+/// assert_eq!(readability.sentences, 2);
+/// assert_eq!(readability.syllables, 10);
+/// assert!(readability.flesch_reading_ease() > 100.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct Scorer {
+    cmudict: Option<CmuDict>,
+}
+
+impl Scorer {
+    /// A scorer guessing every word's syllables from its spelling.
+    pub fn new() -> Self {
+        Scorer::default()
+    }
+
+    /// Count syllables with `cmudict`, guessing only for the words it doesn't
+    /// know.
+    pub fn cmudict(mut self, cmudict: CmuDict) -> Self {
+        self.cmudict = Some(cmudict);
+        self
+    }
+
+    pub fn score(&self, text: &str) -> Readability {
+        let mut readability = Readability {
+            sentences: 0,
+            words: 0,
+            syllables: 0,
+            polysyllables: 0,
+            complex_words: 0,
+            estimated: vec![],
+        };
+        let mut sentences = HashSet::new();
+        let mut previous: Option<usize> = None;
+
+        for token in text::tokenize(text) {
+            let first = previous != Some(token.sentence);
+            previous = Some(token.sentence);
+            sentences.insert(token.sentence);
+
+            let syllables = match self.syllables(&token.word) {
+                Some(syllables) => syllables,
+                None => {
+                    readability.estimated.push(token.clone());
+                    text::estimate_syllables(&token.word)
+                }
+            };
+
+            readability.words += 1;
+            readability.syllables += syllables;
+            if syllables >= 3 {
+                readability.polysyllables += 1;
+                if !(proper_noun(&token, first) || inflected(&token.word, syllables)) {
+                    readability.complex_words += 1;
+                }
+            }
+        }
+
+        readability.sentences = sentences.len();
+        readability
+    }
+
+    fn syllables(&self, word: &str) -> Option<usize> {
+        self.cmudict
+            .as_ref()
+            .and_then(|cmudict| cmudict.pronunciations(word))
+            .and_then(|mut pronunciations| pronunciations.next())
+            .map(|phones| phones.iter().filter(|p| p.is_vowel()).count())
+    }
+}
+
+/// Capitalized words other than the first of a sentence.
+fn proper_noun(token: &Token, first: bool) -> bool {
+    !first && token.word.starts_with(char::is_uppercase)
+}
+
+/// Whether a word of three syllables has only two without its "-ing", or its
+/// "-ed" or "-es" when the "e" is sounded, as in "created" but not
+/// "established".
+fn inflected(word: &str, syllables: usize) -> bool {
+    let word = word.to_lowercase();
+    if syllables != 3 {
+        return false;
+    }
+    if word.ends_with("ing") {
+        return true;
+    }
+    (word.ends_with("ed") || word.ends_with("es"))
+        && text::estimate_syllables(&word) > text::estimate_syllables(&word[..word.len() - 1])
+}
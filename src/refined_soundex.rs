@@ -0,0 +1,46 @@
+use soundex::letters;
+
+/// Refined Soundex phonetic encoding. Unlike American Soundex, vowels are coded,
+/// letters are split into finer groups and the code isn't truncated.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::refined_soundex::encoding("testing"), "T6036084");
+/// assert_eq!(ttaw::refined_soundex::encoding("jumped"), "J408106");
+/// ```
+pub fn encoding(input: &str) -> String {
+    let chars = letters(input);
+
+    let mut code = match chars.first() {
+        Some(c) => c.to_string(),
+        None => return String::new(),
+    };
+
+    let mut last = None;
+
+    for c in chars {
+        let d = digit(c);
+        if Some(d) != last {
+            code.push(d);
+        }
+        last = Some(d);
+    }
+
+    code
+}
+
+fn digit(c: char) -> char {
+    match c {
+        'B' | 'P' => '1',
+        'F' | 'V' => '2',
+        'C' | 'K' | 'S' => '3',
+        'G' | 'J' => '4',
+        'Q' | 'X' | 'Z' => '5',
+        'D' | 'T' => '6',
+        'L' => '7',
+        'M' | 'N' => '8',
+        'R' => '9',
+        _ => '0',
+    }
+}
@@ -0,0 +1,113 @@
+/// Levenshtein edit distance, the number of single character insertions,
+/// deletions and substitutions needed to turn `a` into `b`.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::similarity::levenshtein("kitten", "sitting"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut row = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + if ca == *cb { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Levenshtein distance scaled to a similarity between 0.0 (nothing in common)
+/// and 1.0 (identical).
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::similarity::normalized_levenshtein("Smith", "Smyth"), 0.8);
+/// ```
+pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+
+    if len == 0 {
+        return 1.0;
+    }
+
+    1.0 - levenshtein(a, b) as f64 / len as f64
+}
+
+/// Jaro similarity between 0.0 and 1.0, based on the characters the strings
+/// have in common and how many of those are out of order.
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+
+        for j in start..end {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_common = a.iter().zip(&a_matched).filter(|(_, m)| **m);
+    let b_common = b.iter().zip(&b_matched).filter(|(_, m)| **m);
+    let transpositions = a_common.zip(b_common).filter(|(x, y)| x.0 != y.0).count() / 2;
+
+    let matches = matches as f64;
+    (matches / a.len() as f64
+        + matches / b.len() as f64
+        + (matches - transpositions as f64) / matches)
+        / 3.0
+}
+
+/// Jaro-Winkler similarity between 0.0 and 1.0, Jaro similarity boosted for
+/// strings sharing a prefix of up to four characters. Suited to short strings
+/// like names, where typos rarely hit the first letters.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// let score = ttaw::similarity::jaro_winkler("MARTHA", "MARHTA");
+/// assert!((score - 0.961).abs() < 0.001);
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let jaro = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
@@ -0,0 +1,76 @@
+/// American Soundex phonetic encoding, a letter followed by three digits.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::soundex::encoding("Robert"), "R163");
+/// assert_eq!(ttaw::soundex::encoding("Rupert"), "R163");
+///
+/// assert_eq!(ttaw::soundex::encoding("Tymczak"), "T522");
+/// assert_eq!(ttaw::soundex::encoding("Pfister"), "P236");
+/// ```
+pub fn encoding(input: &str) -> String {
+    let chars = letters(input);
+
+    let first = match chars.first() {
+        Some(c) => *c,
+        None => return String::new(),
+    };
+
+    let mut code = first.to_string();
+    let mut last = digit(first);
+
+    for c in chars.iter().skip(1) {
+        match *c {
+            // H and W don't separate letters with the same code
+            'H' | 'W' => continue,
+            'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => last = None,
+            c => {
+                let d = digit(c);
+                if d.is_some() && d != last {
+                    code.extend(d);
+                }
+                last = d;
+            }
+        }
+    }
+
+    code.extend(std::iter::repeat_n('0', 4));
+    code.truncate(4);
+    code
+}
+
+fn digit(c: char) -> Option<char> {
+    match c {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+/// The uppercased ASCII letters of the input, everything else is dropped.
+pub(crate) fn letters(input: &str) -> Vec<char> {
+    input
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_only() {
+        assert_eq!(
+            letters("O'Hara-Smith 3rd"),
+            "OHARASMITHRD".chars().collect::<Vec<_>>()
+        );
+        assert!(letters("  42 ").is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+/// Spanish phonetic key, a Soundex variant built on Spanish pronunciation
+/// rather than spelling: "b"/"v", "s"/"z"/soft "c", "j"/soft "g" and "ll"/"y"
+/// each sound the same (seseo and yeísmo), "h" is silent and "qu"/"gu" before
+/// "e" or "i" are plain "k"/"g". The key is the first sound followed by three
+/// digits.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw;
+/// assert_eq!(ttaw::spanish::encoding("Valle"), "B900");
+/// assert_eq!(ttaw::spanish::encoding("Valle"), ttaw::spanish::encoding("Baye"));
+/// assert_eq!(ttaw::spanish::encoding("Jiménez"), ttaw::spanish::encoding("Giménez"));
+/// ```
+pub fn encoding(input: &str) -> String {
+    let sounds = sounds(input);
+
+    let first = match sounds.first() {
+        Some(c) => *c,
+        None => return String::new(),
+    };
+
+    let mut code = first.to_string();
+    let mut last = digit(first);
+
+    for c in sounds.iter().skip(1) {
+        let d = digit(*c);
+        if d.is_some() && d != last {
+            code.extend(d);
+        }
+        last = d;
+    }
+
+    code.extend(std::iter::repeat_n('0', 4));
+    code.truncate(4);
+    code
+}
+
+/// Spelling to sounds, each sound is written with a single letter:
+/// B (b, v, w), K (hard c, k, q), G (hard g), J (j, soft g), S (s, z, soft c),
+/// C ("ch"), Y ("ll", consonant y), Ñ and vowels A, E, I, O, U.
+fn sounds(input: &str) -> Vec<char> {
+    let chars = input
+        .to_uppercase()
+        .chars()
+        .filter_map(|c| match c {
+            'Á' => Some('A'),
+            'É' => Some('E'),
+            'Í' => Some('I'),
+            'Ó' => Some('O'),
+            'Ú' | 'Ü' => Some('U'),
+            'Ñ' => Some('Ñ'),
+            c if c.is_ascii_uppercase() => Some(c),
+            _ => None,
+        })
+        .collect::<Vec<char>>();
+
+    let at = |i: usize| chars.get(i).cloned().unwrap_or(' ');
+    let is_vowel = |c: char| matches!(c, 'A' | 'E' | 'I' | 'O' | 'U');
+    let is_front_vowel = |c: char| matches!(c, 'E' | 'I');
+
+    let mut sounds = vec![];
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        let next = at(pos + 1);
+
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => sounds.push(c),
+            'B' | 'V' | 'W' => sounds.push('B'),
+            'C' if next == 'H' => {
+                sounds.push('C');
+                pos += 1;
+            }
+            'C' if is_front_vowel(next) => sounds.push('S'),
+            'C' | 'K' => sounds.push('K'),
+            'Q' => {
+                sounds.push('K');
+                // the "u" in "que" and "qui" is silent
+                if next == 'U' {
+                    pos += 1;
+                }
+            }
+            'G' if is_front_vowel(next) => sounds.push('J'),
+            'G' => {
+                sounds.push('G');
+                // the "u" in "gue" and "gui" is silent
+                if next == 'U' && is_front_vowel(at(pos + 2)) {
+                    pos += 1;
+                }
+            }
+            'J' => sounds.push('J'),
+            'H' => {}
+            'L' if next == 'L' => {
+                sounds.push('Y');
+                pos += 1;
+            }
+            'Y' if is_vowel(next) => sounds.push('Y'),
+            // "y" is a vowel at the end of a word or before a consonant
+            'Y' => sounds.push('I'),
+            'S' | 'Z' => sounds.push('S'),
+            'X' => {
+                sounds.push('K');
+                sounds.push('S');
+            }
+            'R' if next == 'R' => {
+                sounds.push('R');
+                pos += 1;
+            }
+            c => sounds.push(c),
+        }
+
+        pos += 1;
+    }
+
+    sounds
+}
+
+fn digit(c: char) -> Option<char> {
+    match c {
+        'B' | 'P' | 'F' => Some('1'),
+        'K' | 'G' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' | 'Ñ' => Some('5'),
+        'R' => Some('6'),
+        'S' => Some('7'),
+        'J' => Some('8'),
+        'C' | 'Y' => Some('9'),
+        _ => None,
+    }
+}
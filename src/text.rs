@@ -0,0 +1,506 @@
+use std::ops::Range;
+
+/// A word of a text as it's spoken, with the byte span of the text it was
+/// read from. Numbers, currencies and abbreviations are expanded into several
+/// words sharing the same span, e.g. "$5" into "five" and "dollars".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub word: String,
+    /// Byte range in the original text.
+    pub span: Range<usize>,
+    /// Index of the sentence the word is in.
+    pub sentence: usize,
+}
+
+/// Abbreviations read as words, with or without their period.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("dr", "doctor"),
+    ("mr", "mister"),
+    ("mrs", "missus"),
+    ("ms", "miz"),
+    ("prof", "professor"),
+    ("jr", "junior"),
+    ("sr", "senior"),
+    ("st", "saint"),
+    ("mt", "mount"),
+    ("vs", "versus"),
+    ("etc", "et cetera"),
+    ("approx", "approximately"),
+];
+
+/// Expansions of the abbreviations that go before a name, whose period is
+/// never the end of a sentence when a capitalized word follows.
+const TITLES: &[&str] = &[
+    "doctor",
+    "mister",
+    "missus",
+    "miz",
+    "professor",
+    "saint",
+    "mount",
+];
+
+/// Words that usually go unstressed, skipped over when looking for repeated
+/// sounds.
+const FUNCTION_WORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "but", "nor", "so", "if", "than", "then", "of", "in", "on",
+    "at", "to", "for", "with", "by", "from", "as", "into", "onto", "up", "is", "are", "am", "was",
+    "were", "be", "it", "its", "i", "me", "my", "you", "your", "he", "him", "his", "she", "her",
+    "we", "us", "our", "they", "them", "their", "that", "this", "these", "those",
+];
+
+/// Currency symbols with the names of the unit and its hundredth, singular
+/// and plural.
+const CURRENCIES: &[(char, [&str; 4])] = &[
+    ('$', ["dollar", "dollars", "cent", "cents"]),
+    ('£', ["pound", "pounds", "penny", "pence"]),
+    ('€', ["euro", "euros", "cent", "cents"]),
+];
+
+const ONES: &[&str] = &[
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: &[&str] = &[
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: &[&str] = &[
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+/// Split a text into the words it's read as. Words are split on whitespace,
+/// punctuation and hyphens, apostrophes inside a word are kept ("don't") and
+/// "'n'" is read as "and" ("rock'n'roll"). Numbers, ordinals, currencies,
+/// percentages, "&" and common abbreviations are expanded into words, "St."
+/// as "saint" before a name and as "street" after one.
+/// Sentences end at ".", "!" and "?". The period of an abbreviation ends one
+/// only at the end of the text or before a capitalized word, and never after
+/// a title like "Dr." or "St." read as "saint".
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::text::tokenize;
+///
+/// let text = "Dr. Who paid $5 for the 2nd row.";
+/// let words = tokenize(text)
+///     .into_iter()
+///     .map(|t| t.word)
+///     .collect::<Vec<String>>();
+/// assert_eq!(
+///     words,
+///     vec!["doctor", "Who", "paid", "five", "dollars", "for", "the", "second", "row"]
+/// );
+///
+/// let tokens = tokenize(text);
+/// assert_eq!(&text[tokens[4].span.clone()], "$5");
+/// ```
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let chars = text.char_indices().collect::<Vec<(usize, char)>>();
+    let is_alphanumeric = |i: usize| chars.get(i).is_some_and(|(_, c)| c.is_alphanumeric());
+    let is_digit = |i: usize| chars.get(i).is_some_and(|(_, c)| c.is_ascii_digit());
+
+    let mut tokens = vec![];
+    let mut sentence = 0;
+    let mut in_sentence = false;
+    // whether the last word was a capitalized one inside a sentence
+    let mut after_name = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        let currency = CURRENCIES.iter().any(|(symbol, _)| *symbol == c) && is_digit(i + 1);
+
+        if !c.is_alphanumeric() && !currency {
+            match c {
+                '&' => {
+                    tokens.push(Token {
+                        word: "and".to_string(),
+                        span: start..start + c.len_utf8(),
+                        sentence,
+                    });
+                    in_sentence = true;
+                }
+                '.' | '!' | '?' if in_sentence => {
+                    sentence += 1;
+                    in_sentence = false;
+                }
+                _ => {}
+            }
+            if !c.is_whitespace() {
+                after_name = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        while j < chars.len() {
+            let c = chars[j].1;
+            // apostrophes join letters, periods and commas join digits
+            let joins = (is_apostrophe(c) && is_alphanumeric(j - 1) && is_alphanumeric(j + 1))
+                || ((c == '.' || c == ',') && is_digit(j - 1) && is_digit(j + 1));
+            if c.is_alphanumeric() || joins {
+                j += 1;
+            } else {
+                if c == '%' && is_digit(j - 1) {
+                    j += 1;
+                }
+                break;
+            }
+        }
+
+        let mut end = chars.get(j).map_or(text.len(), |(i, _)| *i);
+        let raw = &text[start..end];
+
+        // "rock 'n' roll"
+        let quoted = |i: usize| chars.get(i).is_some_and(|(_, c)| is_apostrophe(*c));
+        if (raw == "n" || raw == "N") && i > 0 && quoted(i - 1) && quoted(j) {
+            tokens.push(Token {
+                word: "and".to_string(),
+                span: chars[i - 1].0..end + chars[j].1.len_utf8(),
+                sentence,
+            });
+            after_name = false;
+            in_sentence = true;
+            i = j + 1;
+            continue;
+        }
+
+        let before_name = chars[j..]
+            .iter()
+            .find(|(_, c)| c.is_alphanumeric())
+            .is_some_and(|(_, c)| c.is_uppercase());
+        let expansion = match abbreviation(raw) {
+            // "St. Louis" but "Main St."
+            Some("saint") if after_name || !before_name => Some("street"),
+            expansion => expansion,
+        };
+        after_name = in_sentence && c.is_uppercase();
+
+        if let Some(expansion) = expansion {
+            // the period of an abbreviation is part of it, and ends the
+            // sentence too when the text does or a new sentence seems to start
+            let period = chars.get(j).map(|(_, c)| *c) == Some('.');
+            if period {
+                end += 1;
+                j += 1;
+            }
+            for word in expansion.split(' ') {
+                tokens.push(Token {
+                    word: word.to_string(),
+                    span: start..end,
+                    sentence,
+                });
+            }
+
+            let last = !chars[j..].iter().any(|(_, c)| c.is_alphanumeric());
+            if period && (last || (before_name && !TITLES.contains(&expansion))) {
+                sentence += 1;
+                in_sentence = false;
+                after_name = false;
+                i = j;
+                continue;
+            }
+        } else {
+            for (range, words) in expand(raw) {
+                for word in words.split(' ') {
+                    tokens.push(Token {
+                        word: word.to_string(),
+                        span: start + range.start..start + range.end,
+                        sentence,
+                    });
+                }
+            }
+        }
+
+        in_sentence = true;
+        i = j;
+    }
+
+    tokens
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+fn abbreviation(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    ABBREVIATIONS
+        .iter()
+        .find(|(abbreviation, _)| *abbreviation == word)
+        .map(|(_, expansion)| *expansion)
+}
+
+/// The words a raw token is read as, each with the range of the token it
+/// came from.
+fn expand(raw: &str) -> Vec<(Range<usize>, String)> {
+    let whole = 0..raw.len();
+
+    if let Some(words) = currency(raw)
+        .or_else(|| percentage(raw))
+        .or_else(|| ordinal_number(raw))
+        .or_else(|| number(raw))
+    {
+        return vec![(whole, words)];
+    }
+
+    // "rock'n'roll"
+    let chars = raw.char_indices().collect::<Vec<(usize, char)>>();
+    let and = chars.windows(3).find(|w| {
+        is_apostrophe(w[0].1) && (w[1].1 == 'n' || w[1].1 == 'N') && is_apostrophe(w[2].1)
+    });
+    if let Some(w) = and {
+        let (start, end) = (w[0].0, w[2].0 + w[2].1.len_utf8());
+        let mut words = vec![
+            (0..start, raw[..start].to_string()),
+            (start..end, "and".to_string()),
+        ];
+        words.extend(
+            expand(&raw[end..])
+                .into_iter()
+                .map(|(range, word)| (range.start + end..range.end + end, word)),
+        );
+        return words;
+    }
+
+    vec![(whole, raw.to_string())]
+}
+
+fn currency(raw: &str) -> Option<String> {
+    let symbol = raw.chars().next()?;
+    let (_, [unit, units, cent, cents]) = CURRENCIES.iter().find(|(s, _)| *s == symbol)?;
+
+    let amount = raw[symbol.len_utf8()..].replace(',', "");
+    let (whole, fraction) = match amount.find('.') {
+        Some(i) if amount.len() - i == 3 => (&amount[..i], amount[i + 1..].parse::<u64>().ok()?),
+        Some(_) => return None,
+        None => (&amount[..], 0),
+    };
+    let whole = whole.parse::<u64>().ok()?;
+
+    let mut words = vec![];
+    if whole > 0 || fraction == 0 {
+        words.push(format!(
+            "{} {}",
+            cardinal(whole),
+            if whole == 1 { unit } else { units }
+        ));
+    }
+    if fraction > 0 {
+        words.push(format!(
+            "{} {}",
+            cardinal(fraction),
+            if fraction == 1 { cent } else { cents }
+        ));
+    }
+    Some(words.join(" "))
+}
+
+fn percentage(raw: &str) -> Option<String> {
+    let number = number(raw.strip_suffix('%')?)?;
+    Some(format!("{} percent", number))
+}
+
+fn ordinal_number(raw: &str) -> Option<String> {
+    let lower = raw.to_lowercase();
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| lower.strip_suffix(suffix))?;
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == ',') {
+        return None;
+    }
+    digits.replace(',', "").parse().ok().map(ordinal)
+}
+
+/// A plain number, with thousands separators or decimals. Numbers that look
+/// like years are read as years ("1999" as "nineteen ninety nine").
+fn number(raw: &str) -> Option<String> {
+    if raw.is_empty()
+        || !raw
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+
+    let (whole, fraction) = match raw.find('.') {
+        Some(i) => (&raw[..i], Some(&raw[i + 1..])),
+        None => (raw, None),
+    };
+    let n = whole.replace(',', "").parse::<u64>().ok()?;
+
+    let mut words = match fraction {
+        None if whole.len() == 4 && is_year(n) => year(n),
+        _ => cardinal(n),
+    };
+    if let Some(fraction) = fraction {
+        words.push_str(" point");
+        for digit in fraction.chars().filter_map(|c| c.to_digit(10)) {
+            words.push(' ');
+            words.push_str(ONES[digit as usize]);
+        }
+    }
+    Some(words)
+}
+
+fn is_year(n: u64) -> bool {
+    (1100..2000).contains(&n) || (2010..2100).contains(&n)
+}
+
+fn year(n: u64) -> String {
+    let (century, rest) = (n / 100, n % 100);
+    match rest {
+        0 => format!("{} hundred", cardinal(century)),
+        1..=9 => format!("{} oh {}", cardinal(century), ONES[rest as usize]),
+        _ => format!("{} {}", cardinal(century), cardinal(rest)),
+    }
+}
+
+/// A number in words, as separate words without hyphens or "and".
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::text::cardinal;
+/// assert_eq!(cardinal(0), "zero");
+/// assert_eq!(cardinal(1_021), "one thousand twenty one");
+/// ```
+pub fn cardinal(n: u64) -> String {
+    if n == 0 {
+        return ONES[0].to_string();
+    }
+
+    let mut groups = vec![];
+    let mut rest = n;
+    let mut scale = 0;
+    while rest > 0 {
+        let group = rest % 1000;
+        if group > 0 {
+            let mut words = hundreds(group);
+            if scale > 0 {
+                words.push(SCALES[scale]);
+            }
+            groups.push(words.join(" "));
+        }
+        rest /= 1000;
+        scale += 1;
+    }
+
+    groups.reverse();
+    groups.join(" ")
+}
+
+fn hundreds(n: u64) -> Vec<&'static str> {
+    let mut words = vec![];
+    if n >= 100 {
+        words.push(ONES[(n / 100) as usize]);
+        words.push("hundred");
+    }
+    match n % 100 {
+        0 => {}
+        n if n < 20 => words.push(ONES[n as usize]),
+        n => {
+            words.push(TENS[(n / 10) as usize]);
+            if n % 10 > 0 {
+                words.push(ONES[(n % 10) as usize]);
+            }
+        }
+    }
+    words
+}
+
+/// An ordinal number in words.
+///
+/// ```rust
+/// extern crate ttaw;
+/// use ttaw::text::ordinal;
+/// assert_eq!(ordinal(2), "second");
+/// assert_eq!(ordinal(40), "fortieth");
+/// assert_eq!(ordinal(121), "one hundred twenty first");
+/// ```
+pub fn ordinal(n: u64) -> String {
+    let words = cardinal(n);
+    let (rest, last) = match words.rfind(' ') {
+        Some(i) => words.split_at(i + 1),
+        None => ("", &words[..]),
+    };
+
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        last if last.ends_with('y') => format!("{}ieth", &last[..last.len() - 1]),
+        last => format!("{}th", last),
+    };
+
+    format!("{}{}", rest, last)
+}
+
+pub(crate) fn is_function_word(word: &str) -> bool {
+    let word = word.to_lowercase();
+    FUNCTION_WORDS.contains(&word.as_str())
+}
+
+/// Syllables of a word guessed from its spelling, the groups of vowel
+/// letters less a silent final "e" and the "e" of "-ed" and "-es" where it
+/// isn't sounded, at least one.
+pub(crate) fn estimate_syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let is_vowel = |c: char| "aeiouy".contains(c);
+
+    let mut groups = 0;
+    let mut previous = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous {
+            groups += 1;
+        }
+        previous = vowel;
+    }
+
+    let silent = if word.ends_with("ed") {
+        !word.ends_with("ted") && !word.ends_with("ded") && !word.ends_with("eed")
+    } else if word.ends_with("es") {
+        let stem = &word[..word.len() - 2];
+        // sounded after a sibilant, as in "boxes", "pages" and "wishes"
+        !stem.ends_with(|c| "sxzcghe".contains(c))
+    } else {
+        word.ends_with('e') && !word.ends_with("le") && !word.ends_with("ee")
+    };
+    if groups > 1 && silent {
+        groups -= 1;
+    }
+
+    groups.max(1)
+}
@@ -0,0 +1,23 @@
+extern crate ttaw;
+
+use ttaw::caverphone::encoding;
+
+#[test]
+fn names() {
+    assert_eq!(encoding("Thompson"), "TMPSN11111");
+    assert_eq!(encoding("Stevenson"), "STFNSN1111");
+    assert_eq!(encoding("Peter"), "PTA1111111");
+    assert_eq!(encoding("Lee"), "LA11111111");
+}
+
+#[test]
+fn similar_names() {
+    assert_eq!(encoding("Tomson"), encoding("Thomson"));
+    assert_eq!(encoding("Stephenson"), encoding("Stevenson"));
+}
+
+#[test]
+fn empty() {
+    assert_eq!(encoding(""), "");
+    assert_eq!(encoding("'"), "");
+}
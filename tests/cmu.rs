@@ -1,10 +1,10 @@
-
 extern crate ttaw;
 
 #[cfg(test)]
 extern crate tempfile;
 
-use ttaw::cmu::CmuDict;
+use ttaw::cmu::{self, Anchor, CmuDict, Normalizer, RhymeOptions};
+use ttaw::Error;
 
 #[test]
 fn cmu_encoding_found() {
@@ -131,4 +131,499 @@ fn quick_brown_fox() {
     assert!(!cmudict.alliteration("jumps", "over").unwrap());
     assert!(!cmudict.alliteration("a", "lazy").unwrap());
     assert!(!cmudict.alliteration("lazy", "dog").unwrap());
-}
\ No newline at end of file
+}
+
+#[test]
+fn encoding_batch() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let words = ["night", "unknown", "mist", "night"]
+        .iter()
+        .cycle()
+        .take(400)
+        .collect::<Vec<&&str>>();
+
+    let encodings = cmudict.encoding_batch(words.iter().map(|w| **w)).unwrap();
+    assert_eq!(encodings.len(), 400);
+    for (word, encoding) in words.iter().zip(encodings) {
+        assert_eq!(encoding, cmudict.encoding(word).unwrap());
+    }
+    assert_eq!(cmudict.encoding_batch(Vec::<String>::new()), Ok(vec![]));
+}
+
+#[test]
+fn shared_handles() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<CmuDict>();
+
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let handles = (0..4)
+        .map(|_| {
+            let cmudict = cmudict.clone();
+            std::thread::spawn(move || cmudict.rhyme("hissed", "mist").unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert!(handle.join().unwrap());
+    }
+}
+
+#[test]
+fn borrowed_pronunciations() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+
+    let mut pronunciations = cmudict.pronunciations("hissed").unwrap();
+    assert_eq!(pronunciations.len(), 1);
+
+    let hissed = pronunciations.next().unwrap();
+    let symbols = hissed.iter().map(|p| p.as_str()).collect::<Vec<&str>>();
+    assert_eq!(symbols, vec!["HH", "IH1", "S", "T"]);
+    assert_eq!(hissed[1].stress(), Some(1));
+    assert!(pronunciations.next().is_none());
+
+    assert!(cmudict.pronunciations("unknown").is_none());
+    assert_eq!(
+        cmudict.encoding("hissed"),
+        Ok(Some(vec![vec![
+            "HH".to_string(),
+            "IH1".to_string(),
+            "S".to_string(),
+            "T".to_string()
+        ]]))
+    );
+}
+
+#[test]
+fn invalid_phoneme() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cmudict.json");
+    std::fs::write(&path, r#"{"hissed": [["HH", "IH9", "S", "T"]]}"#).unwrap();
+
+    let err = CmuDict::new(&path).err().unwrap();
+    assert!(matches!(
+        err,
+        Error::CorruptDictionary { ref source, .. }
+            if **source == Error::InvalidPhoneme("IH9".to_string())
+    ));
+}
+
+#[test]
+fn empty_pronunciations() {
+    for json in &[
+        r#"{"snow": [], "snowman": [["M", "AE1", "N"]]}"#,
+        r#"{"snow": [[]]}"#,
+    ] {
+        let err = CmuDict::builder()
+            .no_cache()
+            .download(false)
+            .embedded(json)
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            Error::CorruptDictionary { ref source, .. }
+                if **source == Error::EmptyPronunciation("snow".to_string())
+        ));
+    }
+}
+
+fn fixture_source() -> cmu::Source {
+    let path = std::fs::canonicalize("tests/fixtures/cmudict.dict").unwrap();
+    cmu::Source::new(&format!("file://{}", path.display()))
+}
+
+const FIXTURE_SHA256: &str = "d905b4757860819440a0827a7c37b6d1ba7a192d78b25a88a818cdfc85d22293";
+
+#[test]
+fn download_from_mirror() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cmudict.json");
+    let source = fixture_source().sha256(&FIXTURE_SHA256.to_uppercase());
+
+    let cmudict = CmuDict::from_source(&path, &source).unwrap();
+    assert!(cmudict.rhyme("tryst", "wrist").unwrap());
+    assert_eq!(cmudict.pronunciations("read").unwrap().len(), 2);
+
+    // only the dictionary is left behind
+    let files = std::fs::read_dir(dir.path()).unwrap().count();
+    assert_eq!(files, 1);
+}
+
+#[test]
+fn checksum_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cmudict.json");
+    let source = fixture_source().sha256(&"0".repeat(64));
+
+    let err = cmu::download_and_serialize_from(&source, &path).unwrap_err();
+    assert!(err.to_string().contains("checksum mismatch"));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn checksum_before_decoding() {
+    let dir = tempfile::tempdir().unwrap();
+    let mirror = dir.path().join("cmudict.dict");
+    std::fs::write(&mirror, b"CAF\xc9  K AE0 F EY1\n").unwrap();
+    let source = cmu::Source::new(&format!("file://{}", mirror.display())).sha256(&"0".repeat(64));
+
+    let err = cmu::download_and_serialize_from(&source, &dir.path().join("cmudict.json"));
+    assert!(matches!(err, Err(Error::ChecksumMismatch { .. })));
+}
+
+#[test]
+fn corrupt_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cmudict.json");
+    std::fs::write(&path, r#"{"hissed": [["HH", "IH1""#).unwrap();
+
+    let err = CmuDict::from_source(&path, &fixture_source())
+        .err()
+        .unwrap();
+    match err {
+        Error::CorruptDictionary {
+            origin,
+            line,
+            source,
+        } => {
+            assert_eq!(origin, path.display().to_string());
+            assert_eq!(line, Some(1));
+            assert!(matches!(*source, Error::Json(_)));
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn corrupt_download() {
+    let dir = tempfile::tempdir().unwrap();
+    let dict = dir.path().join("cmudict.dict");
+    std::fs::write(
+        &dict,
+        "d'artagnan D AH0 R T AE1 NG Y AH0 N # foreign french\nhissed HH IH1 S T\nmist M IH1 XX T\n",
+    )
+    .unwrap();
+    let source = cmu::Source::new(&format!("file://{}", dict.display()));
+
+    let path = dir.path().join("cmudict.json");
+    let err = cmu::download_and_serialize_from(&source, &path).unwrap_err();
+    assert_eq!(
+        err,
+        Error::CorruptDictionary {
+            origin: source.url().to_string(),
+            line: Some(3),
+            source: Box::new(Error::InvalidPhoneme("XX".to_string())),
+        }
+    );
+    assert!(!path.exists());
+
+    // comments are dropped
+    std::fs::write(
+        &dict,
+        "d'artagnan D AH0 R T AE1 NG Y AH0 N # foreign french\n",
+    )
+    .unwrap();
+    let cmudict = CmuDict::from_source(&path, &source).unwrap();
+    assert_eq!(
+        cmudict.lookup("d'artagnan").unwrap().next().unwrap().len(),
+        9
+    );
+}
+
+#[test]
+fn unknown_word() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    assert_eq!(cmudict.lookup("hissed").unwrap().len(), 1);
+    assert_eq!(
+        cmudict.lookup("zzzz").err(),
+        Some(Error::UnknownWord("zzzz".to_string()))
+    );
+}
+
+#[test]
+fn normalization() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+
+    // every entry point looks words up the same way
+    assert_eq!(cmudict.encoding("Fox"), cmudict.encoding("fox"));
+    assert!(cmudict.pronunciations(" \u{201c}Night!\u{201d} ").is_some());
+    assert!(cmudict.lookup("RED,").is_ok());
+    assert!(cmudict.rhyme("Night.", "KNIGHT").unwrap());
+    assert!(cmudict.alliteration("(Brown)", "bears").unwrap());
+
+    let symbols = |word: &str| {
+        cmudict
+            .lookup(word)
+            .unwrap()
+            .map(|p| {
+                p.iter()
+                    .map(|p| p.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(symbols("dog's"), vec!["D AO1 G Z"]);
+    assert_eq!(symbols("Dog\u{2019}s"), vec!["D AO1 G Z"]);
+    assert_eq!(symbols("dogs'"), vec!["D AO1 G Z"]);
+    assert_eq!(symbols("'em"), vec!["AH0 M"]);
+    assert_eq!(symbols("\u{2018}Til,"), vec!["T IH1 L"]);
+    assert_eq!(symbols("'night'"), vec!["N AY1 T"]);
+    assert_eq!(symbols("knights"), vec!["N AY1 T S"]);
+    assert_eq!(symbols("foxes"), vec!["F AA1 K S IH0 Z"]);
+    assert_eq!(symbols("fox's"), vec!["F AA1 K S IH0 Z"]);
+    // in the dictionary as it is
+    assert_eq!(symbols("bears"), vec!["B EH1 R Z"]);
+    assert!(cmudict.lookup("reds'es").is_err());
+    assert!(cmudict.lookup("chaires").is_err());
+    assert!(cmudict.lookup("'s").is_err());
+
+    assert!(cmudict.rhyme("knights", "nights").unwrap());
+    assert!(!cmudict.rhyme("knights", "night").unwrap());
+
+    let exact = cmudict.clone().with_normalizer(Normalizer::exact());
+    assert_eq!(exact.normalizer(), &Normalizer::exact());
+    assert!(exact.pronunciations("Night").is_none());
+    assert!(exact.pronunciations("night").is_some());
+    assert!(exact.pronunciations("dog's").is_none());
+
+    let no_suffixes = CmuDict::builder()
+        .no_cache()
+        .path("tests/fixtures/cmudict.json")
+        .normalizer(Normalizer::new().suffixes(false))
+        .build()
+        .unwrap();
+    assert!(no_suffixes.pronunciations("Night!").is_some());
+    assert!(no_suffixes.pronunciations("dog's").is_none());
+}
+
+#[test]
+fn derivation() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json")
+        .unwrap()
+        .with_normalizer(Normalizer::new().compounds(true));
+    let symbols = |word: &str| {
+        cmudict
+            .lookup(word)
+            .unwrap()
+            .map(|p| {
+                p.iter()
+                    .map(|p| p.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(symbols("misted"), vec!["M IH1 S T IH0 D"]);
+    assert_eq!(symbols("foxed"), vec!["F AA1 K S T"]);
+    assert_eq!(symbols("bedded"), vec!["B EH1 D IH0 D"]);
+    assert_eq!(symbols("bedding"), vec!["B EH1 D IH0 NG"]);
+    assert_eq!(symbols("browner"), vec!["B R AW1 N ER0"]);
+    assert_eq!(symbols("lazily"), vec!["L EY1 Z IY0 L IY0"]);
+    assert_eq!(symbols("redness"), vec!["R EH1 D N AH0 S"]);
+    assert_eq!(symbols("bedchair"), vec!["B EH1 D CH EH2 R"]);
+    assert_eq!(symbols("dog-fox"), vec!["D AO1 G F AA2 K S"]);
+    assert_eq!(cmudict.pronunciations("bedchair").unwrap().len(), 1);
+
+    assert!(cmudict.rhyme("bedding", "redding").unwrap());
+    assert!(cmudict.rhyme("bedchair", "redchair").unwrap());
+    assert_eq!(cmudict.syllables("misted"), Ok(Some(2)));
+    assert_eq!(cmudict.syllables("bedchair"), Ok(Some(2)));
+    assert_eq!(cmudict.syllables("snappy"), Ok(Some(2)));
+    assert_eq!(cmudict.syllables("zzzz"), Ok(None));
+    assert!(cmudict.encoding("redness").unwrap().is_some());
+
+    // parts that are function words are not taken for a compound
+    assert!(cmudict.pronunciations("withdog").is_none());
+    assert!(cmudict.pronunciations("hiscat").is_none());
+
+    // nor are stems too short or without a vowel, though "b" and "x" are words
+    assert!(cmudict.rhyme_verdict("bly", "xed").unwrap().is_unknown());
+    assert_eq!(
+        cmudict.alliteration_verdict("bs", "bed").unwrap().missing(),
+        ["bs"]
+    );
+
+    let no_compounds = cmudict.clone().with_normalizer(Normalizer::new());
+    assert!(no_compounds.pronunciations("bedchair").is_none());
+    assert!(no_compounds.pronunciations("redness").is_some());
+}
+
+#[test]
+fn sources() {
+    assert_eq!(
+        cmu::Source::default().url(),
+        "https://raw.githubusercontent.com/cmusphinx/cmudict/master/cmudict.dict"
+    );
+    assert_eq!(
+        cmu::Source::revision("v0.7b").url(),
+        "https://raw.githubusercontent.com/cmusphinx/cmudict/v0.7b/cmudict.dict"
+    );
+    assert_eq!(
+        cmu::Source::new("http://mirror/cmudict.dict").checksum(),
+        None
+    );
+}
+
+#[test]
+fn directory_path() {
+    let cmudict = CmuDict::new("tests/fixtures").unwrap();
+    assert!(cmudict.rhyme("hissed", "mist").unwrap());
+
+    // a directory without a dictionary gets one downloaded into it
+    let dir = tempfile::tempdir().unwrap();
+    let cmudict = CmuDict::from_source(dir.path(), &fixture_source()).unwrap();
+    assert!(cmudict.pronunciations("read").is_some());
+    assert!(dir.path().join("cmudict.json").is_file());
+}
+
+#[test]
+fn builder_resolution_order() {
+    let cache = tempfile::tempdir().unwrap();
+    let embedded = r#"{"red": [["R", "EH1", "D"]]}"#;
+
+    // embedded first
+    let cmudict = CmuDict::builder()
+        .embedded(embedded)
+        .cache_dir(cache.path())
+        .path("tests/fixtures")
+        .build()
+        .unwrap();
+    assert!(cmudict.pronunciations("red").is_some());
+    assert!(cmudict.pronunciations("mist").is_none());
+
+    // then the explicit path, with nothing cached
+    let cmudict = CmuDict::builder()
+        .cache_dir(cache.path())
+        .path("tests/fixtures")
+        .download(false)
+        .build()
+        .unwrap();
+    assert!(cmudict.pronunciations("mist").is_some());
+
+    // then a download into the cache
+    let cmudict = CmuDict::builder()
+        .cache_dir(cache.path())
+        .source(fixture_source())
+        .build()
+        .unwrap();
+    assert!(cmudict.pronunciations("read").is_some());
+    assert!(cache.path().join("cmudict.json").is_file());
+
+    // the cache is used before the explicit path
+    let cmudict = CmuDict::builder()
+        .cache_dir(cache.path())
+        .path("tests/fixtures")
+        .download(false)
+        .build()
+        .unwrap();
+    assert!(cmudict.pronunciations("read").is_some());
+    assert!(cmudict.pronunciations("night").is_none());
+}
+
+#[test]
+fn builder_download_to_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dict.json");
+
+    let cmudict = CmuDict::builder()
+        .no_cache()
+        .path(&path)
+        .source(fixture_source())
+        .build()
+        .unwrap();
+    assert!(cmudict.rhyme("tryst", "wrist").unwrap());
+    assert!(path.is_file());
+
+    let missing = dir.path().join("missing.json");
+    assert!(CmuDict::builder()
+        .no_cache()
+        .path(missing)
+        .download(false)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn rhyme_options() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let strict = cmudict.clone().with_rhyme_options(RhymeOptions::strict());
+    let pairs = [
+        ("hear", "near"),
+        ("rabbit", "habit"),
+        ("cab", "cap"),
+        ("grease", "breeze"),
+    ];
+    for &(a, b) in &pairs {
+        assert!(!strict.rhyme(a, b).unwrap(), "{} {}", a, b);
+    }
+
+    let loose = cmudict.clone().with_rhyme_options(RhymeOptions::loose());
+    for &(a, b) in &pairs {
+        assert!(loose.rhyme(a, b).unwrap(), "{} {}", a, b);
+    }
+
+    let options = RhymeOptions::new();
+    assert!(cmudict
+        .clone()
+        .with_rhyme_options(options.clone().before_r(true))
+        .rhyme("hear", "near")
+        .unwrap());
+    let primary = options.clone().anchor(Anchor::Primary);
+    assert!(!cmudict
+        .clone()
+        .with_rhyme_options(primary.clone())
+        .rhyme("cargo", "Margo")
+        .unwrap());
+    assert!(cmudict
+        .clone()
+        .with_rhyme_options(primary.ignore_stress(true))
+        .rhyme("cargo", "Margo")
+        .unwrap());
+    // the anchoring vowel keeps its stress
+    assert!(!cmudict
+        .clone()
+        .with_rhyme_options(options.clone().ignore_stress(true))
+        .rhyme("pancake", "mistake")
+        .unwrap());
+    assert!(cmudict
+        .clone()
+        .with_rhyme_options(options.clone().reduced_vowels(true))
+        .rhyme("rabbit", "habit")
+        .unwrap());
+    assert!(!cmudict
+        .clone()
+        .with_rhyme_options(options.clone().reduced_vowels(true))
+        .rhyme("cab", "cap")
+        .unwrap());
+    assert!(cmudict
+        .clone()
+        .with_rhyme_options(options.clone().family(true))
+        .rhyme("grease", "breeze")
+        .unwrap());
+}
+
+#[test]
+fn rhyme_anchor() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let stressed = cmudict.clone().with_rhyme_options(RhymeOptions::new());
+    assert_eq!(stressed.rhyme_options().clone(), RhymeOptions::strict());
+    assert!(stressed.rhyme("sunlight", "moonlight").unwrap());
+    assert!(!stressed.rhyme("happy", "money").unwrap());
+
+    let primary = cmudict
+        .clone()
+        .with_rhyme_options(RhymeOptions::new().anchor(Anchor::Primary));
+    assert!(!primary.rhyme("sunlight", "moonlight").unwrap());
+
+    let weak = cmudict
+        .clone()
+        .with_rhyme_options(RhymeOptions::new().anchor(Anchor::Vowel));
+    assert!(weak.rhyme("happy", "money").unwrap());
+
+    // nothing stressed to rhyme from
+    assert!(!stressed.rhyme("the", "a").unwrap());
+    assert!(weak.rhyme("the", "a").unwrap());
+}
@@ -0,0 +1,68 @@
+extern crate ttaw;
+
+use ttaw::cologne::encoding;
+
+#[test]
+fn reference() {
+    assert_eq!(encoding("Wikipedia"), "3412");
+    assert_eq!(encoding("Müller-Lüdenscheidt"), "65752682");
+    assert_eq!(encoding("Breschnew"), "17863");
+}
+
+#[test]
+fn surnames() {
+    let groups: &[&[&str]] = &[
+        &["Müller", "Mueller", "Muller", "Miller"],
+        &["Meyer", "Maier", "Mayer", "Meier"],
+        &["Schmidt", "Schmitt", "Schmid"],
+        &["Becker", "Bäcker", "Beker"],
+        &["Fischer", "Fisher"],
+        &["Hoffmann", "Hofmann", "Offmann"],
+        &["Weber", "Wéber", "Veber"],
+        &["Schulz", "Schultz", "Schulze"],
+        &["Krause", "Kraus"],
+        &["Koch", "Kock"],
+    ];
+
+    for group in groups {
+        let code = encoding(group[0]);
+        for name in group.iter() {
+            assert_eq!(encoding(name), code, "{} vs {}", name, group[0]);
+        }
+    }
+}
+
+#[test]
+fn sharp_s() {
+    assert_eq!(encoding("Strauß"), "8278");
+    assert_eq!(encoding("Strauß"), encoding("Strauss"));
+    assert_eq!(encoding("STRAUẞ"), encoding("Strauss"));
+}
+
+#[test]
+fn distinct_surnames() {
+    assert_ne!(encoding("Müller"), encoding("Möller-Meier"));
+    assert_ne!(encoding("Schneider"), encoding("Schreiber"));
+    assert_ne!(encoding("Wagner"), encoding("Werner"));
+}
+
+#[test]
+fn c_context() {
+    // initial C
+    assert_eq!(encoding("Cäsar"), "487");
+    assert_eq!(encoding("Celle"), "85");
+    // C after S or Z
+    assert_eq!(encoding("Schacht"), "842");
+    // X after C, K or Q
+    assert_eq!(encoding("Axel"), "0485");
+    assert_eq!(encoding("Dachs"), "248");
+}
+
+#[test]
+fn zeros_and_h() {
+    assert_eq!(encoding("Aue"), "0");
+    assert_eq!(encoding("Ehe"), "0");
+    assert_eq!(encoding("Hamm"), "06");
+    assert_eq!(encoding(""), "");
+    assert_eq!(encoding("--"), "");
+}
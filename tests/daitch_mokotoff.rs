@@ -0,0 +1,46 @@
+extern crate ttaw;
+
+use ttaw::daitch_mokotoff::encoding;
+use ttaw::encoder::{Algorithm, Encoder};
+
+#[test]
+fn single_codes() {
+    assert_eq!(encoding("Moskowitz").to_vec(), vec!["645740"]);
+    assert_eq!(encoding("Lipshitz").to_vec(), vec!["874400"]);
+    assert_eq!(encoding("Auerbach").to_vec(), vec!["097500", "097400"]);
+    assert_eq!(encoding("Ohrbach").to_vec(), vec!["097500", "097400"]);
+    assert_eq!(encoding("Kleinman").to_vec(), vec!["586660"]);
+}
+
+#[test]
+fn branching() {
+    assert_eq!(encoding("Peters").to_vec(), vec!["739400", "734000"]);
+
+    let mut jackson = encoding("Jackson").to_vec();
+    jackson.sort();
+    assert_eq!(jackson, vec!["145460", "154600", "445460", "454600"]);
+}
+
+#[test]
+fn accents_and_spaces() {
+    assert_eq!(encoding("Müller"), encoding("Muller"));
+    assert_eq!(encoding("Van Dyke"), encoding("Vandyke"));
+    assert!(encoding("").is_empty());
+    assert!(encoding("123").is_empty());
+}
+
+#[test]
+fn matching_surnames() {
+    let dm = Algorithm::DaitchMokotoff;
+    assert!(dm.is_match("Moskowitz", "Moskovitz"));
+    assert!(dm.is_match("Schwarz", "Szwarc"));
+    assert!(dm.is_match("Auerbach", "Ohrbach"));
+    assert!(!dm.is_match("Moskowitz", "Peters"));
+}
+
+#[test]
+fn algorithm() {
+    let dm: Algorithm = "Daitch-Mokotoff".parse().unwrap();
+    assert_eq!(dm.encode("Peters"), "739400|734000");
+    assert_eq!(dm.encode_all("Peters"), encoding("Peters"));
+}
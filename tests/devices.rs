@@ -0,0 +1,136 @@
+extern crate ttaw;
+
+use ttaw::cmu::CmuDict;
+use ttaw::devices::{find_alliteration, find_assonance, find_consonance, Device, Finder};
+use ttaw::dialect::Dialect;
+
+fn words(text: &str, runs: &[ttaw::devices::Run]) -> Vec<Vec<String>> {
+    runs.iter()
+        .map(|run| {
+            run.words
+                .iter()
+                .map(|w| text[w.span.clone()].to_string())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn alliteration() {
+    let finder = Finder::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let text = "Peter Piper picked a peck of pickled peppers";
+    let runs = find_alliteration(text);
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].device, Device::Alliteration);
+    assert_eq!(runs[0].sound, "P");
+    assert_eq!(runs[0].span(), 0..text.len());
+
+    let text = "Brown bears, bouncing on a bed. The snappy snails!";
+    let runs = finder.alliteration(text);
+    assert_eq!(
+        words(text, &runs),
+        vec![
+            vec!["Brown", "bears", "bouncing", "bed"],
+            vec!["snappy", "snails"]
+        ]
+    );
+    assert_eq!(runs[0].sound, "B");
+    assert_eq!(runs[1].sound, "S");
+
+    // Double Metaphone for the words the dictionary doesn't know
+    let runs = finder.alliteration("bop brown bears, wild winds");
+    assert_eq!(runs[0].sound, "B");
+    assert_eq!(runs[0].words.len(), 3);
+    assert_eq!(runs[1].sound, "W");
+    assert!(find_alliteration("yellow yams")
+        .iter()
+        .any(|r| r.sound == "Y"));
+}
+
+#[test]
+fn gaps_and_sentences() {
+    let finder = Finder::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let text = "bears and a bed";
+    assert_eq!(finder.alliteration(text).len(), 1);
+    assert!(finder.clone().gap(1).alliteration(text).is_empty());
+    assert!(finder.alliteration("bears, the mist, bed").is_empty());
+    assert_eq!(finder.alliteration("Brown bears. Bouncing beds.").len(), 2);
+    assert!(finder.alliteration("a an the").is_empty());
+}
+
+#[test]
+fn assonance() {
+    let finder = Finder::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let text = "the lazy snails hissed in the mist";
+    let runs = finder.assonance(text);
+    assert_eq!(
+        words(text, &runs),
+        vec![vec!["lazy", "snails"], vec!["hissed", "mist"]]
+    );
+    assert_eq!(runs[0].sound, "EY");
+    assert_eq!(runs[1].sound, "IH");
+
+    // spelled vowels of the words the dictionary doesn't know match the
+    // vowels they can be said as
+    let text = "a bead of seed, the mist and grist";
+    let runs = finder.assonance(text);
+    assert_eq!(
+        words(text, &runs),
+        vec![vec!["bead", "seed"], vec!["mist", "grist"]]
+    );
+    assert_eq!(runs[0].sound, "IY");
+    assert_eq!(runs[1].sound, "IH");
+    assert!(finder.assonance("bead grape").is_empty());
+
+    let runs = find_assonance("fleet feet sweep");
+    assert_eq!(runs[0].sound, "ee");
+    assert_eq!(runs[0].words.len(), 3);
+}
+
+#[test]
+fn dialects() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let finder = Finder::new().cmudict(cmudict.clone());
+    let british = Finder::new().cmudict(cmudict.with_dialect(Dialect::british()));
+
+    // "bath" has the vowel of "father" in British English
+    let text = "a cat in the bath with father";
+    assert_eq!(
+        words(text, &finder.assonance(text)),
+        vec![vec!["cat", "bath"]]
+    );
+    assert_eq!(
+        words(text, &british.assonance(text)),
+        vec![vec!["bath", "father"]]
+    );
+}
+
+#[test]
+fn consonance() {
+    let finder = Finder::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let text = "red bed, bead or the hissed mist";
+    let runs = finder.consonance(text);
+    assert_eq!(
+        words(text, &runs),
+        vec![vec!["red", "bed", "bead"], vec!["hissed", "mist"]]
+    );
+    assert_eq!(runs[0].sound, "D");
+    assert_eq!(runs[1].sound, "T");
+
+    assert_eq!(find_consonance("big dog")[0].sound, "K");
+    assert!(find_consonance("comfy sofa").is_empty());
+}
+
+#[test]
+fn all_devices() {
+    let finder = Finder::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let text = "the lazy snails hissed in the mist";
+    let runs = finder.find_all(text);
+    assert_eq!(
+        runs.iter().map(|r| r.device).collect::<Vec<Device>>(),
+        vec![Device::Assonance, Device::Assonance, Device::Consonance]
+    );
+    assert!(runs
+        .windows(2)
+        .all(|w| w[0].span().start <= w[1].span().start));
+}
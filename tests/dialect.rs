@@ -0,0 +1,129 @@
+extern crate ttaw;
+
+use ttaw::cmu::CmuDict;
+use ttaw::dialect::Dialect;
+use ttaw::phoneme::Phoneme;
+
+fn phones(s: &str) -> Vec<Phoneme> {
+    s.split(' ').map(|p| p.parse().unwrap()).collect()
+}
+
+#[test]
+fn general_american() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json")
+        .unwrap()
+        .with_dialect(Dialect::general_american());
+    assert!(cmudict.rhyme("father", "bother").unwrap());
+    assert!(!cmudict.rhyme("father", "farther").unwrap());
+    assert!(!cmudict.rhyme("caught", "cot").unwrap());
+    assert!(!cmudict.rhyme("bath", "hearth").unwrap());
+    assert!(!cmudict.rhyme("marry", "merry").unwrap());
+}
+
+#[test]
+fn british() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json")
+        .unwrap()
+        .with_dialect(Dialect::british());
+    assert!(!cmudict.rhyme("father", "bother").unwrap());
+    assert!(cmudict.rhyme("father", "farther").unwrap());
+    assert!(cmudict.rhyme("bath", "hearth").unwrap());
+    assert!(cmudict.rhyme("watch", "botch").unwrap());
+    // the word is normalized before it's checked for the vowel of "palm"
+    assert!(cmudict.rhyme("Spa.", "spa").unwrap());
+    assert!(!cmudict.rhyme("caught", "cot").unwrap());
+    assert_eq!(cmudict.dialect(), &Dialect::british());
+    assert_ne!(cmudict.dialect(), &Dialect::australian());
+
+    // pronunciations themselves are left as CMUdict has them
+    assert_eq!(
+        cmudict.encoding("bother").unwrap().unwrap()[0][1],
+        "AA1".to_string()
+    );
+}
+
+#[test]
+fn mergers() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let canadian = cmudict.clone().with_dialect(Dialect::canadian());
+    assert!(canadian.rhyme("caught", "cot").unwrap());
+    assert!(canadian.rhyme("marry", "merry").unwrap());
+    // no merger before "R"
+    assert!(!canadian.rhyme("horse", "farce").unwrap());
+
+    let southern = cmudict.clone().with_dialect(Dialect::southern_american());
+    assert!(southern.rhyme("pin", "pen").unwrap());
+    assert!(!cmudict.rhyme("pin", "pen").unwrap());
+
+    let custom = cmudict.with_dialect(Dialect::new().cot_caught(true));
+    assert!(custom.rhyme("caught", "cot").unwrap());
+    assert!(!custom.rhyme("marry", "merry").unwrap());
+}
+
+#[test]
+fn apply() {
+    let british = Dialect::british();
+    assert_eq!(
+        british.apply("near", &phones("N IH1 R")),
+        phones("N IH1 AH0")
+    );
+    assert_eq!(
+        british.apply("father", &phones("F AA1 DH ER0")),
+        phones("F AA1 DH AH0")
+    );
+    assert_eq!(british.apply("red", &phones("R EH1 D")), phones("R EH1 D"));
+    // "AE" and "S" before a vowel isn't the vowel of "bath"
+    assert_eq!(
+        british.apply("passive", &phones("P AE1 S IH0 V")),
+        phones("P AE1 S IH0 V")
+    );
+    assert_eq!(
+        Dialect::new().apply("bother", &phones("B AA1 DH ER0")),
+        phones("B AA1 DH ER0")
+    );
+}
+
+#[test]
+fn lot() {
+    let british = Dialect::british();
+    let vowel = |word: &str, pronunciation: &str| {
+        let phones = phones(pronunciation);
+        let said = british.apply(word, &phones);
+        phones
+            .iter()
+            .zip(&said)
+            .filter(|(p, _)| p.base() == "AA")
+            .map(|(_, s)| s.base().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(vowel("bothered", "B AA1 DH ER0 D"), vec!["OH"]);
+    assert_eq!(vowel("fathers", "F AA1 DH ER0 Z"), vec!["AA"]);
+    assert_eq!(vowel("grandfather", "G R AE1 N D F AA2 DH ER0"), vec!["AA"]);
+    assert_eq!(vowel("spas", "S P AA1 Z"), vec!["AA"]);
+    // spelled with an "a", which doesn't make it the vowel of "palm"
+    assert_eq!(vowel("hotcake", "HH AA1 T K EY2 K"), vec!["OH"]);
+    assert_eq!(vowel("Oscar", "AA1 S K ER0"), vec!["OH"]);
+    assert_eq!(vowel("watchdog", "W AA1 CH D AA2 G"), vec!["OH", "OH"]);
+    // "AA" before "R"
+    assert_eq!(vowel("starry", "S T AA1 R IY0"), vec!["AA"]);
+    assert_eq!(vowel("sorry", "S AA1 R IY0"), vec!["OH"]);
+    assert_eq!(vowel("cargo", "K AA1 R G OW2"), vec!["AA"]);
+}
+
+#[test]
+fn australian() {
+    let australian = Dialect::australian();
+    assert_eq!(
+        australian.apply("bath", &phones("B AE1 TH")),
+        phones("B AA1 TH")
+    );
+    assert_eq!(
+        australian.apply("dance", &phones("D AE1 N S")),
+        phones("D AE1 N S")
+    );
+    assert_eq!(
+        Dialect::british().apply("dance", &phones("D AE1 N S")),
+        phones("D AA1 N S")
+    );
+}
@@ -0,0 +1,97 @@
+extern crate ttaw;
+
+use ttaw::encoder::{Algorithm, Encoder, PhoneticCodes};
+use ttaw::{
+    caverphone, cologne, daitch_mokotoff, metaphone, nysiis, original_metaphone, refined_soundex,
+    soundex, spanish, Error,
+};
+
+#[test]
+fn dispatch() {
+    let word = "Thompson";
+    assert_eq!(Algorithm::Soundex.encode(word), soundex::encoding(word));
+    assert_eq!(
+        Algorithm::RefinedSoundex.encode(word),
+        refined_soundex::encoding(word)
+    );
+    assert_eq!(Algorithm::Nysiis.encode(word), nysiis::encoding(word));
+    assert_eq!(
+        Algorithm::Metaphone.encode(word),
+        original_metaphone::encoding(word)
+    );
+    assert_eq!(
+        Algorithm::DoubleMetaphone.encode(word),
+        metaphone::encoding(word).primary
+    );
+    assert_eq!(
+        Algorithm::Caverphone.encode(word),
+        caverphone::encoding(word)
+    );
+    assert_eq!(Algorithm::Cologne.encode(word), cologne::encoding(word));
+    assert_eq!(Algorithm::Spanish.encode(word), spanish::encoding(word));
+    assert_eq!(
+        Algorithm::DaitchMokotoff.encode_all(word),
+        daitch_mokotoff::encoding(word)
+    );
+}
+
+#[test]
+fn from_str() {
+    for algorithm in Algorithm::ALL {
+        assert_eq!(algorithm.to_string().parse::<Algorithm>(), Ok(*algorithm));
+    }
+
+    assert_eq!("Refined-Soundex".parse(), Ok(Algorithm::RefinedSoundex));
+    assert_eq!(" double metaphone ".parse(), Ok(Algorithm::DoubleMetaphone));
+    assert_eq!(
+        "soundex2".parse::<Algorithm>(),
+        Err(Error::UnknownAlgorithm("soundex2".to_string()))
+    );
+}
+
+#[test]
+fn is_match() {
+    assert!(Algorithm::Soundex.is_match("Robert", "Rupert"));
+    assert!(!Algorithm::Soundex.is_match("Robert", "Rubin"));
+    assert!(!Algorithm::Soundex.is_match("", ""));
+}
+
+#[test]
+fn encode_all() {
+    assert_eq!(
+        Algorithm::DoubleMetaphone.encode_all("Arnow").to_vec(),
+        vec!["ARN", "ARNF"]
+    );
+    assert_eq!(
+        Algorithm::Soundex.encode_all("Robert").to_vec(),
+        vec!["R163"]
+    );
+    assert!(Algorithm::Soundex.encode_all("").is_empty());
+
+    // matched on the secondary encoding
+    assert!(Algorithm::DoubleMetaphone.is_match("Arnoff", "Arnow"));
+}
+
+#[test]
+fn phonetic_codes() {
+    let codes = PhoneticCodes::new(vec!["739400", "", "734000", "739400"]);
+    assert_eq!(codes.primary(), Some("739400"));
+    assert_eq!(codes.to_string(), "739400|734000");
+    assert!(codes.intersects(&PhoneticCodes::from("734000".to_string())));
+    assert!(!codes.intersects(&PhoneticCodes::default()));
+    assert_eq!(codes.into_vec(), vec!["739400", "734000"]);
+}
+
+struct FirstLetter;
+
+impl Encoder for FirstLetter {
+    fn encode(&self, word: &str) -> String {
+        word.chars().take(1).collect()
+    }
+}
+
+#[test]
+fn custom_encoder() {
+    assert!(FirstLetter.is_match("apple", "avocado"));
+    assert!(!FirstLetter.is_match("apple", "banana"));
+}
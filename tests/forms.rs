@@ -0,0 +1,213 @@
+extern crate ttaw;
+
+use ttaw::cmu::CmuDict;
+use ttaw::forms::{Form, Meter, Problem, Validator};
+use ttaw::phonetic::Verdict;
+
+const HAIKU: &str = "An old silent pond\nA frog jumps into the pond\nSplash! Silence again";
+
+const LIMERICK: &str = "There once was a man from Peru
+Who dreamed he was eating his shoe
+He woke with a fright
+In the middle of the night
+And found it was perfectly true";
+
+#[test]
+fn haiku_and_tanka() {
+    let validator = Validator::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let report = validator.validate(Form::Haiku, HAIKU).unwrap();
+    assert!(report.passed());
+    assert_eq!(
+        report
+            .lines
+            .iter()
+            .map(|l| l.syllables)
+            .collect::<Vec<usize>>(),
+        vec![5, 7, 5]
+    );
+    assert_eq!(
+        &HAIKU[report.lines[2].span.clone()],
+        "Splash! Silence again"
+    );
+
+    let tanka = format!(
+        "{}\n\nA frog jumps into the pond\nA frog jumps into the pond",
+        HAIKU
+    );
+    assert!(validator.validate(Form::Tanka, &tanka).unwrap().passed());
+
+    let report = validator
+        .validate(
+            Form::Haiku,
+            "An old pond\nA frog jumps into the pond\nSplash! Zorblax again",
+        )
+        .unwrap();
+    assert!(!report.passed());
+    assert_eq!(
+        report.lines[0].problems,
+        vec![Problem::Syllables {
+            expected: 5..=5,
+            actual: 3
+        }]
+    );
+    assert_eq!(
+        report.lines[0].problems[0].to_string(),
+        "expected 5 syllables, got 3"
+    );
+    // "Zorblax" is guessed to have two syllables
+    assert_eq!(report.lines[2].unknown, vec!["Zorblax"]);
+    assert!(report.lines[2].problems.is_empty());
+}
+
+#[test]
+fn limerick() {
+    let validator = Validator::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let report = validator.validate(Form::Limerick, LIMERICK).unwrap();
+    assert!(report.passed(), "{:?}", report);
+    assert_eq!(
+        report
+            .lines
+            .iter()
+            .map(|l| l.rhyme)
+            .collect::<Vec<Option<char>>>(),
+        vec![Some('A'), Some('A'), Some('B'), Some('B'), Some('A')]
+    );
+    assert_eq!(report.lines[3].stress, "uu/uuu?");
+
+    let report = validator
+        .validate(
+            Form::Limerick,
+            &LIMERICK
+                .replace("perfectly true", "perfectly red")
+                .replace("In the middle of the night", "eating middle night"),
+        )
+        .unwrap();
+    assert_eq!(
+        report.lines[3].problems,
+        vec![Problem::Meter {
+            meter: Meter::Anapestic,
+            beats: 2
+        }]
+    );
+    assert_eq!(
+        report.lines[4].problems,
+        vec![Problem::Rhyme {
+            letter: 'A',
+            line: 0,
+            verdict: Verdict::No
+        }]
+    );
+    assert_eq!(
+        report.lines[4].problems[0].to_string(),
+        "doesn't rhyme with line 1 (A)"
+    );
+}
+
+#[test]
+fn unknown_line_ending() {
+    let validator = Validator::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    // "guru" isn't in the dictionary, so whether it rhymes is only guessed
+    let report = validator
+        .validate(
+            Form::Limerick,
+            &LIMERICK.replace("perfectly true", "perfectly guru"),
+        )
+        .unwrap();
+    assert!(!report.passed());
+    assert_eq!(
+        report.lines[4].problems,
+        vec![Problem::Rhyme {
+            letter: 'A',
+            line: 0,
+            verdict: Verdict::Fallback {
+                missing: vec!["guru".to_string()],
+                verdict: Box::new(Verdict::Yes),
+            }
+        }]
+    );
+    assert_eq!(
+        report.lines[4].problems[0].to_string(),
+        "can't tell if it rhymes with line 1 (A), unknown words: guru, it does by their spelling"
+    );
+
+    let report = validator
+        .validate(
+            Form::Limerick,
+            &LIMERICK.replace("perfectly true", "perfectly Zorblax"),
+        )
+        .unwrap();
+    assert_eq!(
+        report.lines[4].problems[0].to_string(),
+        "can't tell if it rhymes with line 1 (A), unknown words: Zorblax, it doesn't by their spelling"
+    );
+}
+
+#[test]
+fn sonnet_lines() {
+    let validator = Validator::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let report = validator
+        .validate(Form::ShakespeareanSonnet, HAIKU)
+        .unwrap();
+    assert_eq!(
+        report.problems,
+        vec![Problem::Lines {
+            expected: 14,
+            actual: 3
+        }]
+    );
+    assert_eq!(report.lines[0].rhyme, Some('A'));
+    assert_eq!(
+        report.lines[2].problems[0],
+        Problem::Syllables {
+            expected: 10..=11,
+            actual: 5
+        }
+    );
+    assert_eq!(
+        report.lines[2].problems[1].to_string(),
+        "doesn't scan as iambic meter with 5 beats"
+    );
+    assert_eq!(
+        report.lines[2].problems[2].to_string(),
+        "doesn't rhyme with line 1 (A)"
+    );
+    assert!(!validator
+        .validate(Form::PetrarchanSonnet, "")
+        .unwrap()
+        .passed());
+}
+
+#[test]
+fn sonnet_meter() {
+    let validator = Validator::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let sonnet = |line: &str| {
+        let poem = vec![line; 14].join("\n");
+        validator
+            .validate(Form::ShakespeareanSonnet, &poem)
+            .unwrap()
+    };
+
+    let report = sonnet("the man was in the middle of the night");
+    assert_eq!(report.lines[0].stress, "u?uuu/uuu?");
+    assert!(report.lines[0].problems.is_empty());
+
+    // the stress of "again" falls between the beats
+    let report = sonnet("pond again pond again pond again pond");
+    assert_eq!(report.lines[0].stress, "?u/?u/?u/?");
+    assert_eq!(
+        report.lines[0].problems,
+        vec![Problem::Meter {
+            meter: Meter::Iambic,
+            beats: 5
+        }]
+    );
+
+    let report = sonnet("middle middle middle middle middle");
+    assert_eq!(
+        report.lines[0].problems,
+        vec![Problem::Meter {
+            meter: Meter::Iambic,
+            beats: 5
+        }]
+    );
+}
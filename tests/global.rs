@@ -0,0 +1,30 @@
+extern crate ttaw;
+
+use ttaw::cmu;
+
+// the only test in this binary, so changing the environment and loading the
+// global dictionary can't race with other tests
+#[test]
+fn global_dictionary() {
+    std::env::remove_var(cmu::PATH_VAR);
+    std::env::set_var("XDG_DATA_HOME", "/data");
+    assert_eq!(
+        cmu::default_path(),
+        std::path::Path::new("/data/ttaw/cmudict.json")
+    );
+
+    std::env::set_var(cmu::PATH_VAR, "tests/fixtures/cmudict.json");
+    assert_eq!(
+        cmu::default_path(),
+        std::path::Path::new("tests/fixtures/cmudict.json")
+    );
+
+    assert!(cmu::rhyme("hissed", "mist").unwrap());
+    assert!(!cmu::rhyme("comfy", "chair").unwrap());
+    assert!(cmu::alliteration("snappy", "snails").unwrap());
+    assert!(cmu::encoding("night").unwrap().is_some());
+
+    // loaded once, later changes don't apply
+    std::env::set_var(cmu::PATH_VAR, "missing.json");
+    assert!(cmu::encoding("night").unwrap().is_some());
+}
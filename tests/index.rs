@@ -0,0 +1,251 @@
+extern crate tempfile;
+extern crate ttaw;
+
+use std::fs;
+use std::io::Write;
+use ttaw::index::{PersistentIndex, PhoneticIndex, FORMAT_VERSION};
+use ttaw::similarity::{jaro, jaro_winkler, levenshtein, normalized_levenshtein};
+use ttaw::Error;
+
+fn index() -> PhoneticIndex<u32> {
+    let mut index = PhoneticIndex::new();
+    index.insert(1, "Smith");
+    index.insert(2, "Schmidt");
+    index.insert(3, "Smyth");
+    index.insert(4, "Jones");
+    index.insert(5, "Catherine");
+    index.insert(6, "Kathryn");
+    index
+}
+
+fn ids(index: &PhoneticIndex<u32>, name: &str) -> Vec<u32> {
+    index.query(name).into_iter().map(|m| m.id).collect()
+}
+
+#[test]
+fn query_ranked() {
+    let index = index();
+    assert_eq!(ids(&index, "Smith"), vec![1, 3, 2]);
+    assert_eq!(ids(&index, "Smithe"), vec![1, 3, 2]);
+    assert_eq!(ids(&index, "Johnes"), vec![4]);
+    assert!(ids(&index, "Robert").is_empty());
+
+    let matches = index.query("Smith");
+    assert_eq!(matches[0].name, "Smith");
+    assert_eq!(matches[0].score, 1.0);
+    assert!(matches.windows(2).all(|m| m[0].score >= m[1].score));
+}
+
+#[test]
+fn query_secondary_codes() {
+    let index = index();
+    // "Schmidt" is XMT, with secondary SMT matching the primary of "Smith"
+    let matches = index.query("Schmidt");
+    assert_eq!(matches[0].id, 2);
+    assert!(matches.iter().any(|m| m.id == 1));
+    assert!(matches[0].score > matches[1].score);
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut index = index();
+    assert_eq!(index.len(), 6);
+    assert_eq!(index.get(&4), Some("Jones"));
+
+    assert_eq!(index.insert(4, "Johnson"), Some("Jones".to_string()));
+    assert_eq!(index.len(), 6);
+    assert!(ids(&index, "Jones").is_empty());
+    assert_eq!(ids(&index, "Jonson"), vec![4]);
+
+    assert_eq!(index.remove(&1), Some("Smith".to_string()));
+    assert_eq!(index.remove(&1), None);
+    assert_eq!(ids(&index, "Smith"), vec![3, 2]);
+
+    for id in 2..7 {
+        index.remove(&id);
+    }
+    assert!(index.is_empty());
+}
+
+#[test]
+fn string_ids() {
+    let mut index = PhoneticIndex::new();
+    index.insert("a-1".to_string(), "Thomson");
+    assert_eq!(index.query("Tomson")[0].id, "a-1");
+}
+
+#[test]
+fn similarity_scores() {
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!(levenshtein("", "abc"), 3);
+    assert_eq!(levenshtein("flaw", "lawn"), 2);
+    assert_eq!(normalized_levenshtein("", ""), 1.0);
+    assert_eq!(normalized_levenshtein("abc", "xyz"), 0.0);
+
+    assert!((jaro("MARTHA", "MARHTA") - 0.944).abs() < 0.001);
+    assert!((jaro("DIXON", "DICKSONX") - 0.767).abs() < 0.001);
+    assert!((jaro_winkler("DIXON", "DICKSONX") - 0.813).abs() < 0.001);
+    assert!((jaro_winkler("DWAYNE", "DUANE") - 0.840).abs() < 0.001);
+    assert_eq!(jaro_winkler("same", "same"), 1.0);
+    assert_eq!(jaro("abc", "xyz"), 0.0);
+}
+
+#[test]
+fn persistent_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("names.idx");
+
+    let mut index = PersistentIndex::open(&path).unwrap();
+    assert!(index.index().is_empty());
+    index.insert(1, "Smith").unwrap();
+    index.insert(2, "Schmidt").unwrap();
+    index.insert(3, "Jones").unwrap();
+    assert_eq!(index.remove(&3).unwrap(), Some("Jones".to_string()));
+    assert_eq!(index.remove(&3).unwrap(), None);
+    index.sync().unwrap();
+    drop(index);
+
+    let index = PersistentIndex::<u32>::open(&path).unwrap();
+    assert_eq!(index.index().len(), 2);
+    assert_eq!(index.index().get(&2), Some("Schmidt"));
+    assert_eq!(index.stale(), 2);
+
+    let ids = index
+        .query("Smith")
+        .into_iter()
+        .map(|m| m.id)
+        .collect::<Vec<u32>>();
+    assert_eq!(ids, vec![1, 2]);
+}
+
+#[test]
+fn persistent_appends() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("names.idx");
+
+    let mut index = PersistentIndex::open(&path).unwrap();
+    index.insert("a".to_string(), "Smith").unwrap();
+    let before = fs::read_to_string(&path).unwrap();
+
+    index.insert("b".to_string(), "Jones").unwrap();
+    let after = fs::read_to_string(&path).unwrap();
+
+    assert!(after.starts_with(&before));
+    assert_eq!(after.lines().count(), before.lines().count() + 1);
+}
+
+#[test]
+fn persistent_compaction() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("names.idx");
+
+    let mut index = PersistentIndex::open(&path).unwrap();
+    for id in 0..10 {
+        index.insert(id, "Smith").unwrap();
+    }
+    index.insert(0, "Smyth").unwrap();
+    for id in 1..9 {
+        index.remove(&id).unwrap();
+    }
+    assert_eq!(index.stale(), 17);
+
+    index.compact().unwrap();
+    assert_eq!(index.stale(), 0);
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+    // the temporary file was renamed into place
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    // still appends after compaction
+    index.insert(10, "Smithe").unwrap();
+    drop(index);
+
+    let index = PersistentIndex::<u32>::open(&path).unwrap();
+    assert_eq!(index.index().len(), 3);
+    assert_eq!(index.index().get(&0), Some("Smyth"));
+    assert_eq!(index.index().get(&10), Some("Smithe"));
+    assert_eq!(index.stale(), 0);
+}
+
+#[test]
+fn persistent_interrupted_append() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("names.idx");
+
+    let mut index = PersistentIndex::open(&path).unwrap();
+    index.insert(1, "Smith").unwrap();
+    drop(index);
+
+    let mut contents = fs::read_to_string(&path).unwrap();
+    contents.push_str("[\"+\",\"2\",\"Jo");
+    fs::write(&path, contents).unwrap();
+
+    let mut index = PersistentIndex::<u32>::open(&path).unwrap();
+    assert_eq!(index.index().len(), 1);
+    index.insert(2, "Jones").unwrap();
+    drop(index);
+
+    let index = PersistentIndex::<u32>::open(&path).unwrap();
+    assert_eq!(index.index().get(&2), Some("Jones"));
+}
+
+#[test]
+fn persistent_partial_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("names.idx");
+
+    let mut index = PersistentIndex::open(&path).unwrap();
+    index.insert(1, "Smith").unwrap();
+
+    // what a failed append leaves behind, the next one writes over it
+    let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"[\"+\",\"2\",\"Jo").unwrap();
+    drop(file);
+
+    index.insert(3, "Jones").unwrap();
+    drop(index);
+
+    let index = PersistentIndex::<u32>::open(&path).unwrap();
+    assert_eq!(index.index().len(), 2);
+    assert_eq!(index.index().get(&3), Some("Jones"));
+}
+
+#[test]
+fn persistent_format_errors() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let path = dir.path().join("future.idx");
+    fs::write(&path, "ttaw-phonetic-index 99\n").unwrap();
+    assert_eq!(
+        PersistentIndex::<u32>::open(&path).unwrap_err(),
+        Error::CorruptIndex {
+            path: path.display().to_string(),
+            line: 1,
+            reason: "unsupported version 99".to_string(),
+        }
+    );
+
+    let path = dir.path().join("other.idx");
+    fs::write(&path, "{}\n").unwrap();
+    assert!(matches!(
+        PersistentIndex::<u32>::open(&path),
+        Err(Error::CorruptIndex { line: 1, .. })
+    ));
+
+    let path = dir.path().join("ids.idx");
+    fs::write(
+        &path,
+        format!(
+            "ttaw-phonetic-index {}\n[\"+\",\"x\",\"Smith\",\"SM0\"]\n",
+            FORMAT_VERSION
+        ),
+    )
+    .unwrap();
+    assert_eq!(
+        PersistentIndex::<u32>::open(&path).unwrap_err(),
+        Error::CorruptIndex {
+            path: path.display().to_string(),
+            line: 2,
+            reason: "invalid record id \"x\"".to_string(),
+        }
+    );
+}
@@ -0,0 +1,119 @@
+extern crate ttaw;
+
+use ttaw::linkage::{Link, Linker};
+
+fn records() -> Vec<(u32, &'static str)> {
+    vec![
+        (1, "Smith"),
+        (2, "Jones"),
+        (3, "Smyth"),
+        (4, "Schmidt"),
+        (5, "Johns"),
+        (6, "Robert"),
+        (7, "Smithe"),
+    ]
+}
+
+#[test]
+fn clusters() {
+    let clusters = Linker::new().cluster(records());
+
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].ids, vec![1, 3, 7]);
+    assert_eq!(clusters[1].ids, vec![2, 5]);
+
+    for cluster in &clusters {
+        for link in &cluster.links {
+            assert!(link.score >= 0.85 && link.score <= 1.0);
+            assert!(cluster.ids.contains(&link.a) && cluster.ids.contains(&link.b));
+        }
+    }
+}
+
+#[test]
+fn secondary_blocks() {
+    // "Schmidt" is only blocked with "Smith" through its secondary code
+    let clusters = Linker::new().threshold(0.5).cluster(records());
+    assert_eq!(clusters[0].ids, vec![1, 3, 4, 7]);
+    assert!(clusters[0].links.iter().any(|l| l.a == 1 && l.b == 4));
+}
+
+#[test]
+fn transitive_links() {
+    let clusters = Linker::new().cluster(vec![("a", "Smith"), ("b", "Smyth"), ("c", "Smithe")]);
+    assert_eq!(clusters.len(), 1);
+
+    let link = clusters[0]
+        .links
+        .iter()
+        .find(|l| l.a == "a" && l.b == "b")
+        .cloned();
+    assert_eq!(link.map(|l| (l.a, l.b)), Some(("a", "b")));
+    assert_eq!(clusters[0].links.len(), 3);
+}
+
+#[test]
+fn thresholds() {
+    assert!(Linker::new().threshold(1.0).cluster(records()).is_empty());
+    // the "SM0" block is too big, "JNS" just fits
+    let clusters = Linker::new().max_block_size(2).cluster(records());
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].ids, vec![2, 5]);
+    assert_eq!(
+        Linker::new()
+            .max_block_size(2)
+            .cluster(vec![(1, "Jones"), (2, "Johns")]),
+        Linker::new().cluster(vec![(1, "Jones"), (2, "Johns")])
+    );
+}
+
+#[test]
+fn no_duplicates() {
+    assert!(Linker::new()
+        .cluster(Vec::<(u32, String)>::new())
+        .is_empty());
+    assert!(Linker::new()
+        .cluster(vec![(1, "Smith".to_string()), (2, "Jones".to_string())])
+        .is_empty());
+
+    let clusters = Linker::new().cluster(vec![(1, "Jones"), (2, "Jones")]);
+    assert_eq!(
+        clusters[0].links,
+        vec![Link {
+            a: 1,
+            b: 2,
+            score: 1.0
+        }]
+    );
+}
+
+#[test]
+fn word_order() {
+    let clusters = Linker::new().cluster(vec![
+        (1, "John Smith"),
+        (2, "Smith, John"),
+        (3, "Jon Smyth"),
+        (4, "Jane Doe"),
+        (5, "Doe, Jane"),
+    ]);
+
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].ids, vec![1, 2, 3]);
+    assert_eq!(clusters[1].ids, vec![4, 5]);
+}
+
+#[test]
+fn multi_word_names() {
+    let clusters = Linker::new().cluster(vec![
+        (1, "Catherine Elizabeth Johnson"),
+        (2, "Johnson, Kathryn Elisabeth"),
+        (3, "Catherine Anne Miller"),
+        // two words of three in common isn't enough
+        (4, "Mary Elizabeth Jonson"),
+        (5, "Miller, Katharine Ann"),
+    ]);
+
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].ids, vec![1, 2]);
+    assert_eq!(clusters[1].ids, vec![3, 5]);
+}
@@ -0,0 +1,90 @@
+extern crate ttaw;
+
+use ttaw::cmu::{Anchor, CmuDict, RhymeOptions};
+use ttaw::lyrics::Analyzer;
+
+const VERSE: &str = "lazy bed, lazy red comfy\nhissed mist zzz\nwrist tryst";
+
+#[test]
+fn pairs_and_families() {
+    let analyzer = Analyzer::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let analysis = analyzer.analyze(VERSE);
+
+    assert_eq!(analysis.pairs.len(), 7);
+    let bed = &analysis.pairs[0];
+    assert_eq!((&bed.a.word[..], &bed.b.word[..]), ("bed", "red"));
+    assert_eq!(&VERSE[bed.b.span.clone()], "red");
+    assert_eq!(bed.sound, "EH D");
+    // "lazy" before both
+    assert_eq!(bed.syllables, 3);
+
+    assert_eq!(analysis.families.len(), 2);
+    assert_eq!(analysis.families[0].syllables, 3);
+    let family = &analysis.families[1];
+    assert_eq!(family.sound, "IH S T");
+    assert_eq!(
+        family
+            .words
+            .iter()
+            .map(|w| (&w.word[..], w.line))
+            .collect::<Vec<(&str, usize)>>(),
+        vec![("hissed", 1), ("mist", 1), ("wrist", 2), ("tryst", 2)]
+    );
+    // "hissed mist" and "wrist tryst"
+    assert_eq!(family.syllables, 2);
+
+    assert_eq!(analysis.unknown.len(), 1);
+    assert_eq!(analysis.unknown[0].word, "zzz");
+}
+
+#[test]
+fn density() {
+    let analyzer = Analyzer::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let analysis = analyzer.analyze(VERSE);
+
+    // "comfy" and "zzz" don't rhyme
+    assert_eq!(analysis.total_syllables, 13);
+    assert_eq!(analysis.rhymed_syllables, 10);
+    assert!((analysis.density() - 10.0 / 13.0).abs() < 1e-9);
+
+    let chain = analysis.longest_chain().unwrap();
+    assert_eq!(chain.words.len(), 2);
+    assert_eq!(chain.sound, "EH D");
+
+    let empty = analyzer.analyze("");
+    assert_eq!(empty.density(), 0.0);
+    assert!(empty.longest_chain().is_none());
+}
+
+#[test]
+fn line_window() {
+    let analyzer = Analyzer::new(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let analysis = analyzer.clone().lines(0).analyze(VERSE);
+    assert_eq!(
+        analysis
+            .pairs
+            .iter()
+            .map(|p| (&p.a.word[..], &p.b.word[..]))
+            .collect::<Vec<(&str, &str)>>(),
+        vec![("bed", "red"), ("hissed", "mist"), ("wrist", "tryst")]
+    );
+    assert_eq!(analysis.families.len(), 3);
+
+    // the same word isn't a rhyme, a rhyme doesn't extend across lines
+    let analysis = analyzer.analyze("lazy\nbed lazy\nred");
+    assert_eq!(analysis.pairs.len(), 1);
+    assert_eq!(analysis.pairs[0].syllables, 1);
+}
+
+#[test]
+fn rhyme_options() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let analyzer = Analyzer::new(cmudict.clone());
+    assert!(analyzer.analyze("lazy comfy").pairs.is_empty());
+
+    let weak = RhymeOptions::new().anchor(Anchor::Vowel);
+    let analysis = Analyzer::new(cmudict.with_rhyme_options(weak)).analyze("lazy comfy");
+    assert_eq!(analysis.pairs.len(), 1);
+    assert_eq!(analysis.pairs[0].sound, "IY");
+    assert_eq!(analysis.pairs[0].syllables, 1);
+}
@@ -1,7 +1,8 @@
-
 extern crate ttaw;
 
-use ttaw::metaphone::{alliteration, encoding, rhyme, DoubleMetaphone};
+use ttaw::metaphone::{
+    alliteration, encode_batch, encode_phrase, encoding, rhyme, DoubleMetaphone,
+};
 
 #[test]
 fn alliterates_with_spaces() {
@@ -80,6 +81,48 @@ fn no_rhyme() {
     assert!(!rhyme("empty", "\n"));
 }
 
+#[test]
+fn phrase_rhyme() {
+    assert!(rhyme("drive a car", "far"));
+    assert!(rhyme("near and far,", "into the tar!"));
+    assert!(!rhyme("far and wide", "tar"));
+    assert!(!rhyme("...", "tar"));
+}
+
+#[test]
+fn phrase_alliteration() {
+    assert!(alliteration("bouncing beach balls", "bears"));
+    assert!(alliteration("\"Bouncing\" bears", "bumbling bees"));
+    assert!(!alliteration("lazy dog", "dumb luck"));
+    assert!(!alliteration("--", "bears"));
+}
+
+#[test]
+fn phrase_spans() {
+    let text = "Don't stop-believing";
+    let phrase = encode_phrase(text);
+
+    assert_eq!(
+        phrase
+            .iter()
+            .map(|w| &text[w.span.clone()])
+            .collect::<Vec<_>>(),
+        vec!["Don't", "stop", "believing"]
+    );
+    assert_eq!(phrase[1].encoding, encoding("stop"));
+    assert!(encode_phrase(" \t\n").is_empty());
+}
+
+#[test]
+fn phrase_non_ascii_spans() {
+    let text = "façade, mañana";
+    let phrase = encode_phrase(text);
+
+    assert_eq!(phrase.len(), 2);
+    assert_eq!(&text[phrase[0].span.clone()], "façade");
+    assert_eq!(&text[phrase[1].span.clone()], "mañana");
+}
+
 #[test]
 fn ptah() {
     assert_eq!(
@@ -1132,4 +1175,19 @@ fn detestable() {
 fn vileness() {
     assert_eq!(encoding("vileness").primary, "FLNS");
     assert_eq!(encoding("vileness").secondary, "FLNS");
-}
\ No newline at end of file
+}
+
+#[test]
+fn batch() {
+    let words = (0..1000)
+        .map(|i| format!("{}{}", ["Arnow", "Smith", "Schmidt"][i % 3], i))
+        .collect::<Vec<String>>();
+
+    let encodings = encode_batch(&words);
+    assert_eq!(encodings.len(), words.len());
+    for (word, batched) in words.iter().zip(encodings) {
+        assert_eq!(batched, encoding(word));
+    }
+
+    assert!(encode_batch(Vec::<&str>::new()).is_empty());
+}
@@ -0,0 +1,33 @@
+extern crate ttaw;
+
+use ttaw::nysiis::{encoding, full_encoding};
+
+#[test]
+fn names() {
+    assert_eq!(encoding("Andrew"), "ANDR");
+    assert_eq!(encoding("Bishop"), "BASAP");
+    assert_eq!(encoding("Brian"), "BRAN");
+    assert_eq!(encoding("Capp"), "CAP");
+    assert_eq!(encoding("Knuth"), "NAT");
+    assert_eq!(encoding("Kuhl"), "CAL");
+    assert_eq!(encoding("Phillipson"), "FALAPS");
+}
+
+#[test]
+fn prefixes() {
+    assert_eq!(full_encoding("MacIntosh"), "MCANT");
+    assert_eq!(full_encoding("Schmidt"), "SNAD");
+    assert_eq!(full_encoding("Pfeiffer"), "FAFAR");
+}
+
+#[test]
+fn truncated() {
+    assert_eq!(full_encoding("Robertson"), "RABARTSAN");
+    assert_eq!(encoding("Robertson"), "RABART");
+}
+
+#[test]
+fn empty() {
+    assert_eq!(encoding(""), "");
+    assert_eq!(encoding("--"), "");
+}
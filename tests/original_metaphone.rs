@@ -0,0 +1,39 @@
+extern crate ttaw;
+
+use ttaw::original_metaphone::encoding;
+
+#[test]
+fn initial_exceptions() {
+    assert_eq!(encoding("aeon"), "EN");
+    assert_eq!(encoding("gnome"), "NM");
+    assert_eq!(encoding("knight"), "NT");
+    assert_eq!(encoding("pneumatic"), "NMTK");
+    assert_eq!(encoding("wrack"), "RK");
+    assert_eq!(encoding("white"), "WT");
+    assert_eq!(encoding("Xavier"), "SFR");
+}
+
+#[test]
+fn consonants() {
+    assert_eq!(encoding("thumb"), "0M");
+    assert_eq!(encoding("church"), "XRX");
+    assert_eq!(encoding("christ"), "KRST");
+    assert_eq!(encoding("character"), "XRKTR");
+    assert_eq!(encoding("science"), "SNS");
+    assert_eq!(encoding("judge"), "JJ");
+    assert_eq!(encoding("nation"), "NXN");
+    assert_eq!(encoding("phone"), "FN");
+    assert_eq!(encoding("box"), "BKS");
+}
+
+#[test]
+fn doubled_letters() {
+    assert_eq!(encoding("butter"), "BTR");
+    assert_eq!(encoding("accent"), "AKSNT");
+}
+
+#[test]
+fn empty() {
+    assert_eq!(encoding(""), "");
+    assert_eq!(encoding("  "), "");
+}
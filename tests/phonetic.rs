@@ -0,0 +1,134 @@
+extern crate ttaw;
+
+use ttaw::cmu::CmuDict;
+use ttaw::encoder::Encoder;
+use ttaw::phonetic::{AlliterationChecker, Chain, Metaphone, RhymeChecker, Verdict};
+
+fn rhymes<R: RhymeChecker>(checker: &R, a: &str, b: &str) -> bool {
+    checker.rhyme(a, b).unwrap()
+}
+
+#[test]
+fn metaphone_backend() {
+    assert_eq!(Metaphone.encode("Arnow"), "ARN");
+    assert_eq!(Metaphone.encode_all("Arnow").to_vec(), vec!["ARN", "ARNF"]);
+    assert!(Metaphone.encode_all("").is_empty());
+    assert!(rhymes(&Metaphone, "Far", "Tar"));
+    assert!(Metaphone.alliteration("bouncing", "bears").unwrap());
+}
+
+#[test]
+fn cmu_backend() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    assert_eq!(cmudict.encode(" Night "), "N AY1 T");
+    assert!(cmudict.encode_all("Kowalski").is_empty());
+    assert!(cmudict.is_match("night", "knight"));
+    assert!(rhymes(&cmudict, "hissed", "mist"));
+    assert!(!rhymes(&cmudict, "comfy", "chair"));
+    assert!(AlliterationChecker::alliteration(&cmudict, "snappy", "snails").unwrap());
+}
+
+#[test]
+fn chain_prefers_primary() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let checker = Chain::new(&cmudict, Metaphone);
+
+    // metaphone hears "PT" for both, CMU knows the vowels differ
+    assert!(rhymes(&Metaphone, "bead", "bed"));
+    assert!(!rhymes(&checker, "bead", "bed"));
+
+    // silent letters are no problem for either
+    assert!(rhymes(&checker, "knight", "night"));
+    assert!(checker.alliteration("brown", "bears").unwrap());
+    assert!(!checker.alliteration("lazy", "dog").unwrap());
+}
+
+#[test]
+fn chain_falls_back() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let checker = Chain::new(&cmudict, Metaphone);
+
+    assert!(!rhymes(&cmudict, "bed", "fed"));
+    assert!(rhymes(&checker, "bed", "fed"));
+    assert!(rhymes(&checker, "Kowalski", "Nowalski"));
+    assert!(checker.alliteration("Bartholomew", "bears").unwrap());
+}
+
+#[test]
+fn verdicts() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    assert_eq!(cmudict.rhyme_verdict("knight", "night"), Ok(Verdict::Yes));
+    assert_eq!(cmudict.rhyme_verdict("bead", "bed"), Ok(Verdict::No));
+    assert_eq!(
+        cmudict.rhyme_verdict("Fed", "bed"),
+        Ok(Verdict::Unknown {
+            missing: vec!["Fed".to_string()]
+        })
+    );
+    assert_eq!(
+        cmudict
+            .alliteration_verdict("Bartholomew", "Barnaby")
+            .unwrap()
+            .missing(),
+        ["Bartholomew", "Barnaby"]
+    );
+    assert_eq!(cmudict.alliteration_verdict("again", "a"), Ok(Verdict::No));
+    // a word spelled with a vowel first is still looked up
+    assert_eq!(
+        cmudict.alliteration_verdict("again", "zzqx"),
+        Ok(Verdict::Unknown {
+            missing: vec!["zzqx".to_string()]
+        })
+    );
+    assert_eq!(Metaphone.rhyme_verdict("Far", "Tar"), Ok(Verdict::Yes));
+
+    // pronunciations derived from a stem are reported
+    let verdict = cmudict.rhyme_verdict("mists", "wrists").unwrap();
+    assert_eq!(
+        verdict,
+        Verdict::Derived {
+            derived: vec!["mists".to_string(), "wrists".to_string()],
+            verdict: Box::new(Verdict::Yes),
+        }
+    );
+    assert!(verdict.is_yes() && !verdict.is_unknown());
+    assert_eq!(verdict.derived(), ["mists", "wrists"]);
+    assert_eq!(
+        cmudict.rhyme_verdict("knights", "night").unwrap().derived(),
+        ["knights"]
+    );
+    assert!(cmudict.pronunciations("mists").unwrap().is_derived());
+    assert!(!cmudict.pronunciations("mist").unwrap().is_derived());
+
+    let checker = Chain::new(&cmudict, Metaphone);
+    assert_eq!(checker.rhyme_verdict("bead", "bed"), Ok(Verdict::No));
+    let verdict = checker.rhyme_verdict("bed", "fed").unwrap();
+    assert_eq!(
+        verdict,
+        Verdict::Fallback {
+            missing: vec!["fed".to_string()],
+            verdict: Box::new(Verdict::Yes),
+        }
+    );
+    assert!(verdict.is_yes() && !verdict.is_unknown());
+
+    // nothing left to fall back on
+    let verdict = Chain::new(&cmudict, &cmudict)
+        .alliteration_verdict("bed", "Bartholomew")
+        .unwrap();
+    assert!(verdict.is_unknown() && !verdict.is_yes());
+}
+
+#[test]
+fn chain_encode() {
+    let cmudict = CmuDict::new("tests/fixtures/cmudict.json").unwrap();
+    let checker = Chain::new(&cmudict, Metaphone);
+    assert_eq!(checker.encode("night"), "N AY1 T");
+    assert_eq!(checker.encode("Arnow"), Metaphone.encode("Arnow"));
+    assert!(checker.encode_all("").is_empty());
+
+    // "Kowalski" isn't in the dictionary, so both names are compared by
+    // their metaphone codes
+    assert!(checker.is_match("Kowalski", "Kowalsky"));
+    assert!(!checker.is_match("night", "Kowalski"));
+}
@@ -0,0 +1,62 @@
+extern crate ttaw;
+
+use ttaw::cmu::CmuDict;
+use ttaw::readability::Scorer;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 0.001
+}
+
+#[test]
+fn flesch() {
+    let scorer = Scorer::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let readability = scorer.score("The cat sat on the mat. It was happy.");
+    assert_eq!(readability.sentences, 2);
+    assert_eq!(readability.words, 9);
+    assert_eq!(readability.syllables, 10);
+    assert!(readability.estimated.is_empty());
+    assert!(close(readability.flesch_reading_ease(), 108.2675));
+    assert!(close(readability.flesch_kincaid_grade(), -0.7239));
+}
+
+#[test]
+fn fog_and_smog() {
+    let scorer = Scorer::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    let text = "Everybody celebrated at Canada. The cat created it.";
+    let readability = scorer.score(text);
+    assert_eq!(readability.polysyllables, 4);
+    // "Canada" is a proper noun and "created" is "create" and "-ed"
+    assert_eq!(readability.complex_words, 2);
+    assert_eq!(readability.estimated.len(), 1);
+    assert_eq!(&text[readability.estimated[0].span.clone()], "at");
+    assert!(close(readability.gunning_fog(), 11.6));
+    assert!(close(readability.smog(), 11.2081));
+}
+
+#[test]
+fn abbreviation_sentences() {
+    let scorer = Scorer::new().cmudict(CmuDict::new("tests/fixtures/cmudict.json").unwrap());
+    // the period of "St." ends the first sentence too
+    let readability = scorer.score("He lives on Main St. The cat sat.");
+    assert_eq!(readability.sentences, 2);
+    assert_eq!(readability.words, 8);
+
+    let readability = scorer.score("The cat sat on Main St.");
+    assert_eq!(readability.sentences, 1);
+
+    // but not that of a title
+    let readability = scorer.score("He met Dr. Smith. It was happy.");
+    assert_eq!(readability.sentences, 2);
+}
+
+#[test]
+fn estimates() {
+    let readability = Scorer::new().score("He jumped. The boxes were wished for.");
+    assert_eq!(readability.syllables, 8);
+    assert_eq!(readability.estimated.len(), 7);
+
+    let readability = Scorer::new().score("");
+    assert_eq!(readability.words, 0);
+    assert_eq!(readability.flesch_reading_ease(), 0.0);
+    assert_eq!(readability.smog(), 0.0);
+}
@@ -0,0 +1,45 @@
+extern crate ttaw;
+
+use ttaw::{refined_soundex, soundex};
+
+#[test]
+fn soundex_classic() {
+    assert_eq!(soundex::encoding("Robert"), "R163");
+    assert_eq!(soundex::encoding("Rupert"), "R163");
+    assert_eq!(soundex::encoding("Rubin"), "R150");
+    assert_eq!(soundex::encoding("Ashcraft"), "A261");
+    assert_eq!(soundex::encoding("Ashcroft"), "A261");
+    assert_eq!(soundex::encoding("Tymczak"), "T522");
+    assert_eq!(soundex::encoding("Pfister"), "P236");
+    assert_eq!(soundex::encoding("Honeyman"), "H555");
+}
+
+#[test]
+fn soundex_padding() {
+    assert_eq!(soundex::encoding("Lee"), "L000");
+    assert_eq!(soundex::encoding("a"), "A000");
+}
+
+#[test]
+fn soundex_ignores_non_letters() {
+    assert_eq!(soundex::encoding(" o'hara "), soundex::encoding("OHARA"));
+    assert_eq!(
+        soundex::encoding("Smith-Jones"),
+        soundex::encoding("smithjones")
+    );
+    assert_eq!(soundex::encoding(""), "");
+    assert_eq!(soundex::encoding("1234"), "");
+}
+
+#[test]
+fn refined_soundex() {
+    assert_eq!(refined_soundex::encoding("testing"), "T6036084");
+    assert_eq!(refined_soundex::encoding("The"), "T60");
+    assert_eq!(refined_soundex::encoding("quick"), "Q503");
+    assert_eq!(refined_soundex::encoding("brown"), "B1908");
+    assert_eq!(refined_soundex::encoding("fox"), "F205");
+    assert_eq!(refined_soundex::encoding("jumped"), "J408106");
+    assert_eq!(refined_soundex::encoding("lazy"), "L7050");
+    assert_eq!(refined_soundex::encoding("dogs"), "D6043");
+    assert_eq!(refined_soundex::encoding(""), "");
+}
@@ -0,0 +1,49 @@
+extern crate ttaw;
+
+use ttaw::spanish::encoding;
+
+#[test]
+fn surnames() {
+    let groups: &[&[&str]] = &[
+        &["Valle", "Balle", "Baye", "Vaye"],
+        &["Zapata", "Sapata", "Zapatta"],
+        &["Hernández", "Hernandez", "Ernández"],
+        &["Jiménez", "Giménez", "Jimenes"],
+        &["Llorente", "Yorente"],
+        &["Quintana", "Kintana"],
+        &["Cecilia", "Secilia", "Sesilia"],
+        &["Guerrero", "Guerero"],
+        &["Vázquez", "Vásquez", "Basques"],
+        &["Núñez", "Nuñes"],
+    ];
+
+    for group in groups {
+        let code = encoding(group[0]);
+        for name in group.iter() {
+            assert_eq!(encoding(name), code, "{} vs {}", name, group[0]);
+        }
+    }
+}
+
+#[test]
+fn distinct_surnames() {
+    assert_ne!(encoding("Hernández"), encoding("Fernández"));
+    assert_ne!(encoding("Gómez"), encoding("Jiménez"));
+    assert_ne!(encoding("Martín"), encoding("Marín"));
+    assert_ne!(encoding("Chávez"), encoding("Sánchez"));
+}
+
+#[test]
+fn sounds() {
+    assert_eq!(encoding("Chávez"), "C170");
+    assert_eq!(encoding("Guillén"), "G950");
+    assert_eq!(encoding("Rey"), "R000");
+    assert_eq!(encoding("Ximénez"), "K755");
+    assert_eq!(encoding("Carrasco"), "K672");
+}
+
+#[test]
+fn empty() {
+    assert_eq!(encoding(""), "");
+    assert_eq!(encoding("¿?"), "");
+}
@@ -0,0 +1,136 @@
+extern crate ttaw;
+
+use ttaw::text::{cardinal, ordinal, tokenize, Token};
+
+fn words(text: &str) -> Vec<String> {
+    tokenize(text).into_iter().map(|t| t.word).collect()
+}
+
+#[test]
+fn words_and_spans() {
+    let text = "Don't stop-believing, rock\u{2019}n\u{2019}roll & façade!";
+    let tokens = tokenize(text);
+
+    assert_eq!(
+        tokens.iter().map(|t| &t.word[..]).collect::<Vec<&str>>(),
+        vec![
+            "Don't",
+            "stop",
+            "believing",
+            "rock",
+            "and",
+            "roll",
+            "and",
+            "façade"
+        ]
+    );
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|t| &text[t.span.clone()])
+            .collect::<Vec<&str>>(),
+        vec![
+            "Don't",
+            "stop",
+            "believing",
+            "rock",
+            "\u{2019}n\u{2019}",
+            "roll",
+            "&",
+            "façade"
+        ]
+    );
+    assert!(tokenize(" \t\n...").is_empty());
+
+    let text = "rock 'n' roll";
+    let tokens = tokenize(text);
+    assert_eq!(tokens[1].word, "and");
+    assert_eq!(&text[tokens[1].span.clone()], "'n'");
+    assert_eq!(
+        words("rock \u{2019}N\u{2019} roll"),
+        vec!["rock", "and", "roll"]
+    );
+}
+
+#[test]
+fn numbers() {
+    assert_eq!(words("2nd"), vec!["second"]);
+    assert_eq!(words("the 21st"), vec!["the", "twenty", "first"]);
+    assert_eq!(words("3.14"), vec!["three", "point", "one", "four"]);
+    assert_eq!(words("1,000,001"), vec!["one", "million", "one"]);
+    assert_eq!(words("1999"), vec!["nineteen", "ninety", "nine"]);
+    assert_eq!(words("1905"), vec!["nineteen", "oh", "five"]);
+    assert_eq!(words("2000"), vec!["two", "thousand"]);
+    assert_eq!(words("50%"), vec!["fifty", "percent"]);
+    assert_eq!(words("mp3"), vec!["mp3"]);
+
+    assert_eq!(words("$1"), vec!["one", "dollar"]);
+    assert_eq!(words("$5.50"), vec!["five", "dollars", "fifty", "cents"]);
+    assert_eq!(words("$0.01"), vec!["one", "cent"]);
+    assert_eq!(words("£20"), vec!["twenty", "pounds"]);
+    assert_eq!(words("$"), Vec::<String>::new());
+
+    assert_eq!(cardinal(u64::MAX).split(' ').next(), Some("eighteen"));
+    assert_eq!(cardinal(100), "one hundred");
+    assert_eq!(ordinal(12), "twelfth");
+    assert_eq!(ordinal(1_000), "one thousandth");
+}
+
+#[test]
+fn abbreviations_and_sentences() {
+    let text = "Mr. Smith met Dr Jones. Then St. Louis! Etc.";
+    let tokens = tokenize(text);
+
+    assert_eq!(
+        tokens[0],
+        Token {
+            word: "mister".to_string(),
+            span: 0..3,
+            sentence: 0,
+        }
+    );
+    assert_eq!(tokens[3].word, "doctor");
+    assert_eq!(&text[tokens[3].span.clone()], "Dr");
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|t| (&t.word[..], t.sentence))
+            .skip(5)
+            .collect::<Vec<(&str, usize)>>(),
+        vec![
+            ("Then", 1),
+            ("saint", 1),
+            ("Louis", 1),
+            ("et", 2),
+            ("cetera", 2)
+        ]
+    );
+
+    let words = |text: &str| {
+        tokenize(text)
+            .into_iter()
+            .map(|t| t.word)
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(
+        words("They live on Main St. now"),
+        vec!["They", "live", "on", "Main", "street", "now"]
+    );
+    assert_eq!(
+        words("St. Mary's on Baker St."),
+        vec!["saint", "Mary's", "on", "Baker", "street"]
+    );
+    assert_eq!(words("Paris. St. Louis"), vec!["Paris", "saint", "Louis"]);
+
+    let sentences = |text: &str| {
+        tokenize(text)
+            .into_iter()
+            .map(|t| t.sentence)
+            .collect::<Vec<usize>>()
+    };
+    assert_eq!(
+        sentences("on Main St. The next day"),
+        vec![0, 0, 0, 1, 1, 1]
+    );
+    assert_eq!(sentences("Smith Jr. and Mr. Jones"), vec![0, 0, 0, 0, 0]);
+}